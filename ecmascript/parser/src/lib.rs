@@ -301,6 +301,8 @@ pub enum JscTarget {
     Es2018,
     #[serde(rename = "es2019")]
     Es2019,
+    #[serde(rename = "es2020")]
+    Es2020,
 }
 
 impl Default for JscTarget {
@@ -418,6 +418,26 @@ fn issue_380() {
     );
 }
 
+#[test]
+fn async_generator_method() {
+    let e = expr("({ async *f() { await g(); yield 1; } })");
+    let function = match *e {
+        Expr::Paren(ParenExpr { ref expr, .. }) => match **expr {
+            Expr::Object(ObjectLit { ref props, .. }) => match props[0] {
+                PropOrSpread::Prop(ref prop) => match **prop {
+                    Prop::Method(MethodProp { ref function, .. }) => function.clone(),
+                    _ => unreachable!("expected a method, got {:?}", prop),
+                },
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    };
+    assert!(function.is_async);
+    assert!(function.is_generator);
+}
+
 #[bench]
 fn bench_new_expr_ts(b: &mut Bencher) {
     bench_parser(
@@ -226,6 +226,7 @@ impl<'a, I: Tokens> ParseObject<'a, Box<Expr>> for Parser<'a, I> {
         // get a(){}
         // set a(v){}
         // async a(){}
+        // async *a(){}
 
         match ident.sym {
             js_word!("get") | js_word!("set") | js_word!("async") => {
@@ -233,6 +234,9 @@ impl<'a, I: Tokens> ParseObject<'a, Box<Expr>> for Parser<'a, I> {
                     self.emit_err(modifiers_span, SyntaxError::TS1042);
                 }
 
+                // async *a(){}
+                let is_generator = ident.sym == js_word!("async") && eat!('*');
+
                 let key = self.parse_prop_name()?;
                 let key_span = key.span();
 
@@ -337,7 +341,7 @@ impl<'a, I: Tokens> ParseObject<'a, Box<Expr>> for Parser<'a, I> {
                             start,
                             |p| p.parse_unique_formal_params(),
                             true,
-                            false,
+                            is_generator,
                         )
                         .map(|function| {
                             PropOrSpread::Prop(Box::new(Prop::Method(MethodProp { key, function })))
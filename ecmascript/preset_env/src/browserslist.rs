@@ -0,0 +1,514 @@
+//! Discovery of browserslist config files.
+//!
+//! Reading files is abstracted by [ConfigSource], so embedders running on an
+//! async runtime can discover config files themselves and pass the contents
+//! to [Query::from_browserslist_contents].
+
+use crate::{Query, Version, Versions};
+use serde_json::Value;
+use std::{fmt, fs, path::Path};
+
+/// Provides contents of config files.
+pub trait ConfigSource {
+    /// Returns the contents of the file at `path`, or `None` if it does not
+    /// exist.
+    fn read(&self, path: &Path) -> Option<String>;
+}
+
+/// Reads config files from the file system using `std::fs`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FsConfigSource;
+
+impl ConfigSource for FsConfigSource {
+    fn read(&self, path: &Path) -> Option<String> {
+        fs::read_to_string(path).ok()
+    }
+}
+
+/// Searches `dir` and its ancestors for browserslist config, and returns it
+/// in the format of `.browserslistrc`.
+///
+/// In each directory, `.browserslistrc` and `browserslist` take precedence
+/// over the `browserslist` key of `package.json`.
+pub fn find_config<S: ConfigSource>(source: &S, dir: &Path) -> Option<String> {
+    let mut dir = Some(dir);
+
+    while let Some(d) = dir {
+        for name in &[".browserslistrc", "browserslist"] {
+            if let Some(contents) = source.read(&d.join(name)) {
+                return Some(contents);
+            }
+        }
+
+        if let Some(contents) = source.read(&d.join("package.json")) {
+            if let Some(config) = package_json_config(&contents) {
+                return Some(config);
+            }
+        }
+
+        dir = d.parent();
+    }
+
+    None
+}
+
+/// Converts the `browserslist` key of `package.json` to the format of
+/// `.browserslistrc`.
+fn package_json_config(contents: &str) -> Option<String> {
+    fn lines(v: &Value) -> String {
+        match *v {
+            Value::String(ref s) => s.clone(),
+            Value::Array(ref v) => v
+                .iter()
+                .filter_map(|v| v.as_str())
+                .collect::<Vec<_>>()
+                .join("\n"),
+            _ => String::new(),
+        }
+    }
+
+    let json: Value = serde_json::from_str(contents).ok()?;
+
+    match *json.get("browserslist")? {
+        Value::Object(ref envs) => Some(
+            envs.iter()
+                .map(|(env, v)| format!("[{}]\n{}", env, lines(v)))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        ),
+        ref v => Some(lines(v)),
+    }
+}
+
+/// Parses the contents of `.browserslistrc` and returns queries for `env`.
+///
+/// If `env` is `None`, `production` is used. Queries outside of a section are
+/// used if there's no section for `env`.
+pub fn parse_config(contents: &str, env: Option<&str>) -> Query {
+    let env = env.unwrap_or("production");
+
+    let mut defaults = vec![];
+    let mut selected: Option<Vec<String>> = None;
+    let mut in_env = false;
+    let mut in_section = false;
+
+    for line in contents.lines() {
+        let line = match line.find('#') {
+            Some(idx) => &line[..idx],
+            None => line,
+        };
+
+        for query in line.split(',') {
+            let query = query.trim();
+            if query.is_empty() {
+                continue;
+            }
+
+            if query.starts_with('[') && query.ends_with(']') {
+                in_section = true;
+                in_env = query[1..query.len() - 1]
+                    .split_whitespace()
+                    .any(|name| name == env);
+                if in_env && selected.is_none() {
+                    selected = Some(vec![]);
+                }
+                continue;
+            }
+
+            if in_env {
+                selected.as_mut().unwrap().push(query.to_string());
+            } else if !in_section {
+                defaults.push(query.to_string());
+            }
+        }
+    }
+
+    Query::Multiple(selected.unwrap_or(defaults))
+}
+
+/// An error from resolving targets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// `browserslist` failed to resolve the query.
+    QueryFailed,
+    /// The entry is not in the form of `<browser> <version>`.
+    InvalidEntry(String),
+    /// The browser is not known to browserslist.
+    UnknownBrowser(String),
+    /// The version is not a version or a range of versions.
+    InvalidVersion(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::QueryFailed => write!(f, "browserslist failed to resolve the query"),
+            Error::InvalidEntry(ref s) => write!(f, "invalid browserslist entry `{}`", s),
+            Error::UnknownBrowser(ref s) => write!(f, "unknown browser `{}`", s),
+            Error::InvalidVersion(ref s) => write!(f, "invalid browser version `{}`", s),
+        }
+    }
+}
+
+/// Maps a browser name of browserslist to a key of [Versions].
+///
+/// Returns `Some("")` for browsers which are not tracked by the compat data.
+fn browser_key(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "chrome" | "and_chr" => "chrome",
+        "firefox" | "and_ff" => "firefox",
+        "ie" | "ie_mob" => "ie",
+        "ios" | "ios_saf" => "ios",
+        "opera" | "op_mob" => "opera",
+        "edge" => "edge",
+        "safari" => "safari",
+        "node" => "node",
+        "samsung" => "samsung",
+        "android" => "android",
+        "electron" => "electron",
+        "phantom" => "phantom",
+        "and_qq" | "and_uc" | "baidu" | "bb" | "kaios" | "op_mini" => "",
+        _ => return None,
+    })
+}
+
+/// Parses `58`, `12.2`, `4.4.3` and ranges like `12.2-12.4`, which resolve to
+/// their lowest version.
+///
+/// Returns `Ok(None)` for `TP` (technology preview of safari), which is newer
+/// than any released version. `all` resolves to the lowest possible version.
+fn parse_resolved_version(v: &str) -> Result<Option<Version>, ()> {
+    if v.eq_ignore_ascii_case("tp") {
+        return Ok(None);
+    }
+    if v == "all" {
+        return Ok(Some(Version {
+            major: 0,
+            minor: 0,
+            patch: 0,
+        }));
+    }
+
+    let v = v.split('-').next().unwrap();
+    let mut parts = [0u16; 3];
+    let mut len = 0;
+    for part in v.split('.') {
+        if len == parts.len() {
+            return Err(());
+        }
+        parts[len] = part.parse().map_err(|_| ())?;
+        len += 1;
+    }
+
+    Ok(Some(Version {
+        major: parts[0],
+        minor: parts[1],
+        patch: parts[2],
+    }))
+}
+
+impl Versions {
+    /// Creates targets from a list already resolved by `browserslist`, like
+    /// `["chrome 58", "ie 11", "ios_saf 12.2-12.4"]`.
+    ///
+    /// The lowest version of each browser is used as the target.
+    pub fn from_resolved(entries: &[&str]) -> Result<Self, Error> {
+        let mut data = Versions::default();
+        for entry in entries {
+            data.add_resolved(entry)?;
+        }
+
+        Ok(data)
+    }
+
+    pub(crate) fn add_resolved(&mut self, entry: &str) -> Result<(), Error> {
+        let mut parts = entry.split_whitespace();
+        let (name, version) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(name), Some(version), None) => (name, version),
+            _ => return Err(Error::InvalidEntry(entry.into())),
+        };
+
+        let key = browser_key(name).ok_or_else(|| Error::UnknownBrowser(name.into()))?;
+        if key.is_empty() {
+            return Ok(());
+        }
+
+        let version =
+            parse_resolved_version(version).map_err(|_| Error::InvalidVersion(entry.into()))?;
+        let version = match version {
+            Some(version) => version,
+            None => return Ok(()),
+        };
+
+        for (k, v) in self.iter_mut() {
+            if k == key && v.map(|v| v > version).unwrap_or(true) {
+                *v = Some(version);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Resolves targets from the browserslist config nearest to `dir`.
+    ///
+    /// The `defaults` query is used if there's no config file. It's passed
+    /// explicitly, as `browserslist` would otherwise look for config files
+    /// itself.
+    pub fn from_config_source<S: ConfigSource>(
+        source: &S,
+        dir: &Path,
+        env: Option<&str>,
+    ) -> Result<Self, Error> {
+        match find_config(source, dir) {
+            Some(contents) => Query::from_browserslist_contents(&contents, env).exec(),
+            None => Query::Single("defaults".into()).exec(),
+        }
+    }
+}
+
+impl Query {
+    /// Creates a query from the contents of `.browserslistrc`.
+    ///
+    /// This neither touches the file system nor invokes `browserslist`, so
+    /// it does not block. The query is resolved when it's used as
+    /// `Targets`. The `defaults` query is used if `contents` has no query for
+    /// `env`.
+    pub fn from_browserslist_contents(contents: &str, env: Option<&str>) -> Self {
+        match parse_config(contents, env) {
+            Query::Multiple(ref q) if q.is_empty() => Query::Single("defaults".into()),
+            q => q,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fxhash::FxHashMap;
+    use std::{convert::TryInto, path::PathBuf};
+
+    #[derive(Default)]
+    struct MockSource {
+        files: FxHashMap<PathBuf, String>,
+    }
+
+    impl MockSource {
+        fn with(mut self, path: &str, contents: &str) -> Self {
+            self.files.insert(path.into(), contents.into());
+            self
+        }
+    }
+
+    impl ConfigSource for MockSource {
+        fn read(&self, path: &Path) -> Option<String> {
+            self.files.get(path).cloned()
+        }
+    }
+
+    fn queries(v: &[&str]) -> Query {
+        Query::Multiple(v.iter().map(|s| s.to_string()).collect())
+    }
+
+    #[test]
+    fn contents_defaults() {
+        assert_eq!(
+            parse_config(
+                "# comment\n> 1%, last 2 versions\n\nnot dead # trailing",
+                None
+            ),
+            queries(&["> 1%", "last 2 versions", "not dead"])
+        );
+    }
+
+    #[test]
+    fn contents_without_queries() {
+        assert_eq!(
+            Query::from_browserslist_contents("# comment\n", None),
+            Query::Single("defaults".into())
+        );
+    }
+
+    #[test]
+    fn contents_env() {
+        let config = "> 1%\n[production staging]\nie 11\n[development]\nlast 1 chrome version";
+
+        assert_eq!(parse_config(config, None), queries(&["ie 11"]));
+        assert_eq!(parse_config(config, Some("staging")), queries(&["ie 11"]));
+        assert_eq!(
+            parse_config(config, Some("development")),
+            queries(&["last 1 chrome version"])
+        );
+        assert_eq!(parse_config(config, Some("test")), queries(&["> 1%"]));
+    }
+
+    #[test]
+    fn source_walks_up() {
+        let source = MockSource::default()
+            .with("/project/.browserslistrc", "ie 11")
+            .with("/.browserslistrc", "chrome 79");
+
+        assert_eq!(
+            find_config(&source, Path::new("/project/src/components")),
+            Some("ie 11".into())
+        );
+        assert_eq!(
+            find_config(&source, Path::new("/other")),
+            Some("chrome 79".into())
+        );
+        assert_eq!(
+            find_config(&MockSource::default(), Path::new("/project")),
+            None
+        );
+    }
+
+    #[test]
+    fn source_package_json() {
+        let source = MockSource::default()
+            .with("/a/package.json", r#"{ "name": "a" }"#)
+            .with(
+                "/a/b/package.json",
+                r#"{ "browserslist": ["ie 11", "chrome 79"] }"#,
+            )
+            .with(
+                "/c/package.json",
+                r#"{ "browserslist": { "production": ["ie 11"], "development": "chrome 79" } }"#,
+            );
+
+        assert_eq!(find_config(&source, Path::new("/a")), None);
+        assert_eq!(
+            parse_config(&find_config(&source, Path::new("/a/b")).unwrap(), None),
+            queries(&["ie 11", "chrome 79"])
+        );
+
+        let config = find_config(&source, Path::new("/c")).unwrap();
+        assert_eq!(parse_config(&config, None), queries(&["ie 11"]));
+        assert_eq!(
+            parse_config(&config, Some("development")),
+            queries(&["chrome 79"])
+        );
+    }
+
+    #[test]
+    fn resolved_browserslist_output() {
+        let versions = Versions::from_resolved(&[
+            "and_chr 79",
+            "and_ff 68",
+            "and_qq 1.2",
+            "and_uc 12.12",
+            "android 76",
+            "baidu 7.12",
+            "chrome 79",
+            "chrome 78",
+            "edge 18",
+            "firefox 71",
+            "ie 11",
+            "ie_mob 11",
+            "ios_saf 13.2",
+            "ios_saf 12.2-12.4",
+            "kaios 2.5",
+            "op_mini all",
+            "op_mob 46",
+            "opera 64",
+            "safari TP",
+            "safari 13",
+            "samsung 10.1",
+        ])
+        .unwrap();
+
+        let version = |major, minor, patch| {
+            Some(Version {
+                major,
+                minor,
+                patch,
+            })
+        };
+
+        assert_eq!(versions.chrome, version(78, 0, 0));
+        assert_eq!(versions.firefox, version(68, 0, 0));
+        assert_eq!(versions.ie, version(11, 0, 0));
+        assert_eq!(versions.ios, version(12, 2, 0));
+        assert_eq!(versions.opera, version(46, 0, 0));
+        assert_eq!(versions.safari, version(13, 0, 0));
+        assert_eq!(versions.samsung, version(10, 1, 0));
+        assert_eq!(versions.android, version(76, 0, 0));
+        assert_eq!(versions.edge, version(18, 0, 0));
+        assert_eq!(versions.node, None);
+    }
+
+    #[test]
+    fn resolved_all() {
+        let versions =
+            Versions::from_resolved(&["op_mini all", "chrome all", "chrome 79"]).unwrap();
+
+        assert_eq!(
+            versions.chrome,
+            Some(Version {
+                major: 0,
+                minor: 0,
+                patch: 0,
+            })
+        );
+        assert_eq!(
+            Versions::parse_versions(vec!["op_mini all", "ie 11"].into_iter())
+                .unwrap()
+                .ie,
+            Some(Version {
+                major: 11,
+                minor: 0,
+                patch: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn resolved_errors() {
+        assert_eq!(
+            Versions::from_resolved(&["chrome"]).unwrap_err(),
+            Error::InvalidEntry("chrome".into())
+        );
+        assert_eq!(
+            Versions::from_resolved(&["netscape 4"]).unwrap_err(),
+            Error::UnknownBrowser("netscape".into())
+        );
+        assert_eq!(
+            Versions::from_resolved(&["ie eleven"]).unwrap_err(),
+            Error::InvalidVersion("ie eleven".into())
+        );
+    }
+
+    #[test]
+    fn resolved_targets_config() {
+        let config: crate::Config = serde_json::from_str(
+            r#"{ "targets": { "resolved": ["chrome 58", "ios_saf 10.0-10.2"] } }"#,
+        )
+        .unwrap();
+        let versions: Versions = config.targets.try_into().unwrap();
+
+        assert_eq!(
+            versions.chrome,
+            Some(Version {
+                major: 58,
+                minor: 0,
+                patch: 0
+            })
+        );
+        assert_eq!(
+            versions.ios,
+            Some(Version {
+                major: 10,
+                minor: 0,
+                patch: 0
+            })
+        );
+        assert_eq!(versions.ie, None);
+    }
+
+    #[test]
+    fn source_rc_precedence() {
+        let source = MockSource::default()
+            .with("/a/package.json", r#"{ "browserslist": ["chrome 79"] }"#)
+            .with("/a/browserslist", "ie 11");
+
+        assert_eq!(find_config(&source, Path::new("/a")), Some("ie 11".into()));
+    }
+}
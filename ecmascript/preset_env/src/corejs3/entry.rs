@@ -36,6 +36,19 @@ static MODULES_BY_VERSION: Lazy<FxHashMap<Version, Vec<&'static str>>> = Lazy::n
         .collect()
 });
 
+/// Position of each module in the load order of core-js.
+pub(crate) static MODULE_ORDER: Lazy<FxHashMap<&'static str, usize>> = Lazy::new(|| {
+    let mut versions = MODULES_BY_VERSION.iter().collect::<Vec<_>>();
+    versions.sort_by_key(|(v, _)| **v);
+
+    versions
+        .into_iter()
+        .flat_map(|(_, modules)| modules.iter().cloned())
+        .enumerate()
+        .map(|(i, m)| (m, i))
+        .collect()
+});
+
 #[derive(Debug)]
 pub struct Entry {
     is_any_target: bool,
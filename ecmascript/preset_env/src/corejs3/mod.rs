@@ -1,4 +1,7 @@
-pub(crate) use self::{entry::Entry, usage::UsageVisitor};
+pub(crate) use self::{
+    entry::{Entry, MODULE_ORDER},
+    usage::UsageVisitor,
+};
 
 mod compat;
 mod data;
@@ -4,7 +4,11 @@
 #![feature(trace_macros)]
 #![recursion_limit = "256"]
 
-pub use self::{transform_data::Feature, version::Version};
+pub use self::{
+    browserslist::{find_config, parse_config, ConfigSource, Error, FsConfigSource},
+    transform_data::Feature,
+    version::Version,
+};
 use chashmap::CHashMap;
 use fxhash::{FxHashMap, FxHashSet};
 use once_cell::sync::Lazy;
@@ -13,18 +17,21 @@ use st_map::StaticMap;
 use std::{
     convert::{TryFrom, TryInto},
     process::Command,
+    sync::Arc,
 };
 use swc_atoms::{js_word, JsWord};
 use swc_common::{chain, Fold, FoldWith, FromVariant, VisitWith, DUMMY_SP};
 use swc_ecma_ast::*;
 use swc_ecma_transforms::{
     compat::{es2015, es2016, es2017, es2018, es3},
+    helpers::InjectHelpers,
     pass::{noop, Optional, Pass},
     util::prepend_stmts,
 };
 
 #[macro_use]
 mod util;
+mod browserslist;
 mod corejs2;
 mod corejs3;
 mod regenerator;
@@ -32,6 +39,19 @@ mod transform_data;
 mod version;
 
 pub fn preset_env(c: Config) -> impl Pass {
+    preset_env_inner(c, None)
+}
+
+/// Creates a preset which reports required polyfills to `reporter` instead of
+/// injecting imports.
+///
+/// This is useful for bundlers, which can merge the reports of all modules
+/// using [merge_polyfill_sets] and emit the polyfills once.
+pub fn preset_env_with_reporter(c: Config, reporter: PolyfillReporter) -> impl Pass {
+    preset_env_inner(c, Some(reporter))
+}
+
+fn preset_env_inner(c: Config, reporter: Option<PolyfillReporter>) -> impl Pass {
     let loose = c.loose;
     let targets: Versions = c.targets.try_into().expect("failed to parse targets");
     let is_any_target = targets.is_any_target();
@@ -85,7 +105,11 @@ pub fn preset_env(c: Config) -> impl Pass {
         es2015::TemplateLiteral::default(),
         true
     );
-    let pass = add!(pass, Classes, es2015::Classes::default());
+    let pass = add!(
+        pass,
+        Classes,
+        es2015::classes(es2015::classes::Config { loose })
+    );
     let pass = add!(
         pass,
         Spread,
@@ -96,7 +120,7 @@ pub fn preset_env(c: Config) -> impl Pass {
     let pass = add!(pass, ArrowFunctions, es2015::arrow());
     let pass = add!(pass, DuplicateKeys, es2015::duplicate_keys());
     let pass = add!(pass, StickyRegex, es2015::StickyRegex);
-    // TODO:    InstanceOf,
+    let pass = add!(pass, InstanceOf, es2015::instance_of(Default::default()));
     let pass = add!(pass, TypeOfSymbol, es2015::TypeOfSymbol);
     let pass = add!(pass, ShorthandProperties, es2015::Shorthand);
     let pass = add!(pass, Parameters, es2015::parameters());
@@ -111,7 +135,7 @@ pub fn preset_env(c: Config) -> impl Pass {
     let pass = add!(
         pass,
         ComputedProperties,
-        es2015::computed_properties(),
+        es2015::computed_properties(Default::default()),
         true
     );
     let pass = add!(
@@ -121,7 +145,12 @@ pub fn preset_env(c: Config) -> impl Pass {
         true
     );
     let pass = add!(pass, Regenerator, es2015::regenerator(), true);
-    let pass = add!(pass, BlockScoping, es2015::block_scoping(), true);
+    let pass = add!(
+        pass,
+        BlockScoping,
+        es2015::block_scoping(Default::default()),
+        true
+    );
 
     // TODO:
     //    Literals,
@@ -135,7 +164,15 @@ pub fn preset_env(c: Config) -> impl Pass {
     //    NamedCapturingGroupsRegex,
 
     // ES 3
-    let pass = add!(pass, PropertyLiterals, es3::PropertyLiteral);
+    // Engines which need `transform-reserved-words` also choke on keys which
+    // are reserved in es5.
+    let pass = add!(
+        pass,
+        PropertyLiterals,
+        es3::PropertyLiteral {
+            es5_compat: should_enable!(ReservedWords, false)
+        }
+    );
     let pass = add!(pass, MemberExpressionLiterals, es3::MemberExprLit);
     let pass = add!(
         pass,
@@ -163,10 +200,39 @@ pub fn preset_env(c: Config) -> impl Pass {
             targets,
             includes: included_modules,
             excludes: excluded_modules,
-        }
+            reporter,
+        },
+        InjectHelpers,
     )
 }
 
+/// Receives the polyfills required by a module.
+pub type PolyfillReporter = Arc<dyn Fn(Vec<JsWord>) + Send + Sync>;
+
+/// Merges polyfills reported by multiple modules into a deduplicated list.
+///
+/// Modules of core-js 3 are sorted in the load order of core-js, so that
+/// dependencies (e.g. `es.symbol`) come before their dependants (e.g.
+/// `es.symbol.iterator`). Other modules are placed after them, in the order
+/// they are first reported.
+pub fn merge_polyfill_sets(reports: Vec<Vec<JsWord>>) -> Vec<JsWord> {
+    let mut seen = FxHashSet::default();
+    let mut merged = reports
+        .into_iter()
+        .flatten()
+        .filter(|src| seen.insert(src.clone()))
+        .collect::<Vec<_>>();
+
+    merged.sort_by_key(|src| {
+        corejs3::MODULE_ORDER
+            .get(src.trim_start_matches("core-js/modules/"))
+            .cloned()
+            .unwrap_or(usize::max_value())
+    });
+
+    merged
+}
+
 /// A map without allocation.
 #[derive(Debug, Default, Deserialize, Clone, Copy, StaticMap)]
 #[serde(deny_unknown_fields)]
@@ -199,7 +265,6 @@ pub struct BrowserData<T: Default> {
     pub opera_mobile: T,
 }
 
-#[derive(Debug)]
 struct Polyfills {
     mode: Option<Mode>,
     targets: Versions,
@@ -208,6 +273,7 @@ struct Polyfills {
     regenerator: bool,
     includes: FxHashSet<String>,
     excludes: FxHashSet<String>,
+    reporter: Option<PolyfillReporter>,
 }
 
 impl Fold<Module> for Polyfills {
@@ -275,7 +341,9 @@ impl Fold<Module> for Polyfills {
             }))
             .collect::<Vec<_>>();
 
-        if cfg!(debug_assertions) {
+        if let Some(ref reporter) = self.reporter {
+            reporter(required);
+        } else if cfg!(debug_assertions) {
             let mut v = required.into_iter().collect::<Vec<_>>();
             v.sort();
             prepend_stmts(
@@ -348,44 +416,9 @@ impl BrowserData<Option<Version>> {
     }
 
     pub fn parse_versions<'a>(lines: impl Iterator<Item = &'a str>) -> Result<Self, &'a str> {
-        fn remap(key: &str) -> String {
-            match key {
-                "and_chr" => "chrome".into(),
-                "and_ff" => "firefox".into(),
-                "ie_mob" => "ie".into(),
-                "ios_saf" => "ios".into(),
-                "op_mob" => "opera".into(),
-                _ => key.into(),
-            }
-        }
-
-        let browsers = lines.map(|v| {
-            let mut v = v.split(' ');
-            (remap(v.next().unwrap()), v.next().unwrap().to_string())
-        });
-
         let mut data: Versions = BrowserData::default();
-        for (browser, version) in browsers {
-            match &*browser {
-                "and_qq" | "and_uc" | "baidu" | "bb" | "kaios" | "op_mini" => continue,
-
-                _ => {}
-            }
-
-            let version = if version.contains("-") {
-                version.split('-').next().unwrap().parse().unwrap()
-            } else {
-                version.parse().unwrap()
-            };
-
-            // lowest version
-            if data[&browser].is_none() || data[&browser].unwrap() > version {
-                for (k, v) in data.iter_mut() {
-                    if browser == k {
-                        *v = Some(version);
-                    }
-                }
-            }
+        for line in lines {
+            data.add_resolved(line).map_err(|_| line)?;
         }
 
         Ok(data)
@@ -469,6 +502,7 @@ pub enum Targets {
     Query(Query),
     EsModules(EsModules),
     Versions(Versions),
+    Resolved(ResolvedTargets),
     HashMap(FxHashMap<String, QueryOrVersion>),
 }
 
@@ -477,6 +511,14 @@ pub struct EsModules {
     esmodules: bool,
 }
 
+/// Output of `browserslist` resolved by the caller, e.g.
+/// `{ "resolved": ["chrome 58", "ie 11"] }`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ResolvedTargets {
+    pub resolved: Vec<String>,
+}
+
 #[derive(Debug, Clone, Deserialize, FromVariant)]
 #[serde(untagged)]
 pub enum QueryOrVersion {
@@ -491,7 +533,7 @@ pub enum Query {
     Multiple(Vec<String>),
 }
 
-type QueryResult = Result<Versions, ()>;
+type QueryResult = Result<Versions, Error>;
 
 impl Query {
     fn exec(&self) -> QueryResult {
@@ -514,7 +556,7 @@ impl Query {
                 );
                 if !output.status.success() {
                     println!("query.js: Status {:?}", output.status,);
-                    return Err(());
+                    return Err(Error::QueryFailed);
                 }
 
                 output.stdout
@@ -522,10 +564,7 @@ impl Query {
 
             let browsers: Vec<String> =
                 serde_json::from_slice(&output).expect("failed to read browser data output");
-            let versions = BrowserData::parse_versions(browsers.iter().map(|s| &**s))
-                .expect("failed to parse browser version");
-
-            Ok(versions)
+            Versions::from_resolved(&browsers.iter().map(|s| &**s).collect::<Vec<_>>())
         }
 
         static CACHE: Lazy<CHashMap<Query, QueryResult>> = Lazy::new(Default::default);
@@ -533,7 +572,7 @@ impl Query {
         if let Some(v) = CACHE.get(self) {
             return match &*v {
                 Ok(v) => Ok(*v),
-                Err(err) => Err(*err),
+                Err(err) => Err(err.clone()),
             };
         }
 
@@ -542,25 +581,29 @@ impl Query {
             Query::Multiple(ref s) => query(&s),
         };
 
-        CACHE.insert(self.clone(), result);
+        CACHE.insert(self.clone(), result.clone());
 
         result
     }
 }
 
 impl TryFrom<Option<Targets>> for Versions {
-    type Error = ();
+    type Error = Error;
 
     fn try_from(v: Option<Targets>) -> Result<Self, Self::Error> {
         match v {
             None => Ok(Default::default()),
             Some(Targets::Versions(v)) => Ok(v),
             Some(Targets::Query(q)) => q.exec(),
+            Some(Targets::Resolved(r)) => {
+                Versions::from_resolved(&r.resolved.iter().map(|s| &**s).collect::<Vec<_>>())
+            }
             Some(Targets::HashMap(mut map)) => {
-                let q = map.remove("browsers").map(|q| match q {
-                    QueryOrVersion::Query(q) => q.exec().expect("failed to run query"),
-                    _ => unreachable!(),
-                });
+                let q = match map.remove("browsers") {
+                    Some(QueryOrVersion::Query(q)) => Some(q.exec()?),
+                    Some(..) => unreachable!(),
+                    None => None,
+                };
 
                 let node = map.remove("node").map(|q| match q {
                     QueryOrVersion::Version(v) => v,
@@ -583,7 +626,7 @@ impl TryFrom<Option<Targets>> for Versions {
 
 #[cfg(test)]
 mod tests {
-    use super::Query;
+    use super::{merge_polyfill_sets, Query};
 
     #[test]
     fn test_empty() {
@@ -593,4 +636,36 @@ mod tests {
             "empty query should return non-empty result"
         );
     }
+
+    #[test]
+    fn merge_reports() {
+        let merged = merge_polyfill_sets(vec![
+            vec![
+                "core-js/modules/es.array.iterator".into(),
+                "core-js/modules/es.promise".into(),
+                "regenerator-runtime/runtime".into(),
+            ],
+            vec![
+                "core-js/modules/es.symbol.iterator".into(),
+                "core-js/modules/es.array.iterator".into(),
+            ],
+            vec![
+                "core-js/modules/es.promise".into(),
+                "core-js/modules/es.symbol".into(),
+                "core-js/modules/web.dom-collections.iterator".into(),
+            ],
+        ]);
+
+        assert_eq!(
+            merged.iter().map(|s| &**s).collect::<Vec<_>>(),
+            vec![
+                "core-js/modules/es.symbol",
+                "core-js/modules/es.symbol.iterator",
+                "core-js/modules/es.array.iterator",
+                "core-js/modules/es.promise",
+                "core-js/modules/web.dom-collections.iterator",
+                "regenerator-runtime/runtime",
+            ]
+        );
+    }
 }
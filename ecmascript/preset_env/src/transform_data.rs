@@ -67,6 +67,9 @@ pub enum Feature {
     /// `transform-block-scoping`
     BlockScoping,
 
+    /// `transform-instanceof`
+    InstanceOf,
+
     /// `transform-typeof-symbol`
     TypeOfSymbol,
 
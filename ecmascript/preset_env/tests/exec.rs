@@ -0,0 +1,125 @@
+#![feature(box_syntax)]
+
+use std::{
+    env,
+    fs::{create_dir_all, write},
+    path::Path,
+    process::Command,
+};
+use swc_common::{chain, fold::FoldWith, input::SourceFileInput, FileName};
+use swc_ecma_codegen::Emitter;
+use swc_ecma_parser::{Parser, Session, Syntax};
+use swc_ecma_preset_env::{preset_env, Config};
+use swc_ecma_transforms::{
+    fixer,
+    helpers::{Helpers, HELPERS},
+    hygiene, resolver,
+    util::HANDLER,
+};
+use testing::Tester;
+
+/// Compiles `src` with `config` and runs it using node.
+///
+/// `src` should exit with non-zero status on failure.
+fn exec(test_name: &str, config: &str, src: &str) {
+    if env::var("EXEC").unwrap_or_default() == "0" {
+        return;
+    }
+
+    let config: Config = serde_json::from_str(config).expect("failed to parse config");
+
+    let output = Tester::new()
+        .print_errors(|cm, handler| {
+            let fm = cm.new_source_file(
+                FileName::Real(format!("{}.js", test_name).into()),
+                src.into(),
+            );
+            let mut p = Parser::new(
+                Session { handler: &handler },
+                Syntax::default(),
+                SourceFileInput::from(&*fm),
+                None,
+            );
+            let module = p.parse_module().map_err(|mut e| e.emit())?;
+
+            let module = HANDLER.set(&handler, || {
+                HELPERS.set(&Helpers::new(false), || {
+                    let mut pass = chain!(resolver(), preset_env(config), hygiene(), fixer());
+                    module.fold_with(&mut pass)
+                })
+            });
+
+            let mut buf = vec![];
+            {
+                let mut emitter = Emitter {
+                    cfg: swc_ecma_codegen::Config { minify: false },
+                    comments: None,
+                    cm: cm.clone(),
+                    wr: box swc_ecma_codegen::text_writer::JsWriter::new(
+                        cm.clone(),
+                        "\n",
+                        &mut buf,
+                        None,
+                    ),
+                    handlers: box MyHandlers,
+                };
+
+                emitter.emit_module(&module).expect("failed to emit module");
+            }
+
+            Ok(String::from_utf8(buf).expect("invalid utf8"))
+        })
+        .expect("failed to compile");
+    println!("{}", output);
+
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("target")
+        .join("testing");
+    create_dir_all(&dir).expect("failed to create a directory");
+    let path = dir.join(format!("{}.js", test_name));
+    write(&path, &output).expect("failed to write output");
+
+    let status = Command::new("node")
+        .arg(&path)
+        .status()
+        .expect("failed to run node");
+    assert!(status.success(), "Execution failed");
+}
+
+struct MyHandlers;
+
+impl swc_ecma_codegen::Handlers for MyHandlers {}
+
+#[test]
+fn async_arrow_in_method_ie11() {
+    exec(
+        "async_arrow_in_method_ie11",
+        r#"{ "targets": { "ie": 11 } }"#,
+        r#"
+class Foo {
+    constructor() {
+        this.name = "foo";
+    }
+
+    method() {
+        return (async (value) => {
+            await null;
+            return [this, value];
+        })(1);
+    }
+}
+
+const foo = new Foo();
+foo.method()
+    .then(([self, value]) => {
+        if (self !== foo || value !== 1) {
+            throw new Error("wrong this: " + self);
+        }
+    })
+    .catch((err) => {
+        console.error(err);
+        process.exit(1);
+    });
+"#,
+    );
+}
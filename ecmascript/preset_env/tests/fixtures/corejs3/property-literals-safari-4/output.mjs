@@ -0,0 +1,5 @@
+var obj = {
+  let: 1,
+  a: 2,
+  1: 3
+};
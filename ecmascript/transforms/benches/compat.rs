@@ -175,7 +175,7 @@ fn es2015_block_scoped_fn(b: &mut Bencher) {
 
 #[bench]
 fn es2015_block_scoping(b: &mut Bencher) {
-    tr!(b, || compat::es2015::block_scoping());
+    tr!(b, || compat::es2015::block_scoping(Default::default()));
 }
 
 #[bench]
@@ -185,7 +185,7 @@ fn es2015_classes(b: &mut Bencher) {
 
 #[bench]
 fn es2015_computed_props(b: &mut Bencher) {
-    tr!(b, compat::es2015::computed_properties);
+    tr!(b, || compat::es2015::computed_properties(Default::default()));
 }
 
 #[bench]
@@ -215,7 +215,7 @@ fn es2015_for_of(b: &mut Bencher) {
 
 #[bench]
 fn es2015_instanceof(b: &mut Bencher) {
-    tr!(b, || compat::es2015::InstanceOf);
+    tr!(b, || compat::es2015::instance_of(Default::default()));
 }
 
 #[bench]
@@ -1,6 +1,12 @@
 //! New-generation javascript to old-javascript compiler.
 
-pub use self::{es2015::es2015, es2016::es2016, es2017::es2017, es2018::es2018, es3::es3};
+pub use self::{
+    es2015::{es2015, regenerator},
+    es2016::es2016,
+    es2017::es2017,
+    es2018::es2018,
+    es3::es3,
+};
 
 pub mod es2015;
 pub mod es2016;
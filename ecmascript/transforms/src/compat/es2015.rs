@@ -1,10 +1,22 @@
 pub use self::{
-    arrow::arrow, block_scoped_fn::BlockScopedFns, block_scoping::block_scoping, classes::Classes,
-    computed_props::computed_properties, destructuring::destructuring,
-    duplicate_keys::duplicate_keys, for_of::for_of, function_name::function_name,
-    instanceof::InstanceOf, parameters::parameters, regenerator::regenerator,
-    shorthand_property::Shorthand, spread::spread, sticky_regex::StickyRegex,
-    template_literal::TemplateLiteral, typeof_symbol::TypeOfSymbol,
+    arrow::arrow,
+    block_scoped_fn::BlockScopedFns,
+    block_scoping::block_scoping,
+    classes::{classes, Classes},
+    computed_props::computed_properties,
+    destructuring::destructuring,
+    duplicate_keys::duplicate_keys,
+    for_of::for_of,
+    function_name::function_name,
+    instanceof::{instance_of, InstanceOf},
+    new_target::new_target,
+    parameters::parameters,
+    regenerator::regenerator,
+    shorthand_property::Shorthand,
+    spread::spread,
+    sticky_regex::StickyRegex,
+    template_literal::TemplateLiteral,
+    typeof_symbol::TypeOfSymbol,
 };
 use crate::pass::Pass;
 use ast::Expr;
@@ -13,14 +25,15 @@ use swc_common::chain;
 
 mod arrow;
 mod block_scoped_fn;
-mod block_scoping;
+pub mod block_scoping;
 pub mod classes;
-mod computed_props;
+pub mod computed_props;
 pub mod destructuring;
 mod duplicate_keys;
 pub mod for_of;
 mod function_name;
-mod instanceof;
+pub mod instanceof;
+mod new_target;
 mod parameters;
 mod regenerator;
 mod shorthand_property;
@@ -29,13 +42,13 @@ mod sticky_regex;
 mod template_literal;
 mod typeof_symbol;
 
-fn exprs() -> impl Pass {
+fn exprs(c: instanceof::Config) -> impl Pass {
     chain_at!(
         Expr,
         arrow(),
         duplicate_keys(),
         StickyRegex,
-        InstanceOf,
+        instance_of(c),
         TypeOfSymbol,
         Shorthand,
     )
@@ -44,31 +57,44 @@ fn exprs() -> impl Pass {
 /// Compiles es2015 to es5.
 pub fn es2015(c: Config) -> impl Pass {
     chain!(
+        new_target(),
         BlockScopedFns,
         TemplateLiteral::default(),
-        Classes::default(),
+        classes(c.classes),
         spread(c.spread),
         function_name(),
-        exprs(),
+        exprs(c.instanceof),
         parameters(),
         for_of(c.for_of),
-        computed_properties(),
+        computed_properties(c.computed_props),
         destructuring(c.destructuring),
         regenerator(),
-        block_scoping(),
+        block_scoping(c.block_scoping),
     )
 }
 
 #[derive(Debug, Default, Deserialize)]
 pub struct Config {
+    #[serde(flatten)]
+    pub classes: classes::Config,
+
     #[serde(flatten)]
     pub for_of: for_of::Config,
 
+    #[serde(flatten)]
+    pub instanceof: instanceof::Config,
+
+    #[serde(flatten)]
+    pub computed_props: computed_props::Config,
+
     #[serde(flatten)]
     pub destructuring: destructuring::Config,
 
     #[serde(flatten)]
     pub spread: spread::Config,
+
+    #[serde(flatten)]
+    pub block_scoping: block_scoping::Config,
 }
 
 #[cfg(test)]
@@ -1,8 +1,9 @@
 use crate::{pass::Pass, util::undefined};
 use ast::*;
+use serde::Deserialize;
 use smallvec::SmallVec;
 use std::mem::replace;
-use swc_common::{util::map::Map, Fold, FoldWith, Spanned, Visit, VisitWith, DUMMY_SP};
+use swc_common::{util::map::Map, Fold, FoldWith, Span, Spanned, Visit, VisitWith, DUMMY_SP};
 use utils::{find_ids, ident::IdentLike, prepend, var::VarCollector, ExprFactory, Id, StmtLike};
 
 ///
@@ -18,8 +19,23 @@ use utils::{find_ids, ident::IdentLike, prepend, var::VarCollector, ExprFactory,
 /// 	});
 /// }
 /// ```
-pub fn block_scoping() -> impl Pass {
-    BlockScoping::default()
+pub fn block_scoping(c: Config) -> impl Pass {
+    BlockScoping {
+        c,
+        scope: Default::default(),
+        vars: Default::default(),
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    /// Insert runtime checks for accesses to `let` / `const` bindings in
+    /// their temporal dead zone.
+    ///
+    /// This is disabled by default because it increases the size of output.
+    #[serde(default)]
+    pub tdz: bool,
 }
 
 type ScopeStack = SmallVec<[ScopeKind; 8]>;
@@ -37,8 +53,8 @@ enum ScopeKind {
     Block,
 }
 
-#[derive(Default)]
 struct BlockScoping {
+    c: Config,
     scope: ScopeStack,
     vars: Vec<VarDeclarator>,
 }
@@ -364,6 +380,7 @@ where
     Vec<T>: FoldWith<Self>,
 {
     fn fold(&mut self, stmts: Vec<T>) -> Vec<T> {
+        let stmts = if self.c.tdz { check_tdz(stmts) } else { stmts };
         let mut stmts = stmts.fold_children(self);
 
         if !self.vars.is_empty() {
@@ -381,6 +398,324 @@ where
     }
 }
 
+/// Replaces reads of `let` / `const` bindings which occur lexically before
+/// their declaration.
+///
+/// Reads from the same function always throw, so they are replaced with
+/// `_tdzError("x")`. Reads from nested functions may happen after the
+/// declaration, so they are wrapped with `_temporalRef(x, "x")` and the
+/// binding is initialized with a sentinel at the start of the block.
+fn check_tdz<T>(stmts: Vec<T>) -> Vec<T>
+where
+    T: StmtLike,
+{
+    let decls = stmts
+        .iter()
+        .map(|stmt| match stmt.as_stmt() {
+            Some(Stmt::Decl(Decl::Var(VarDecl { kind, decls, .. })))
+                if *kind != VarDeclKind::Var =>
+            {
+                decls.iter().map(|d| find_ids(&d.name)).collect()
+            }
+            _ => vec![],
+        })
+        .collect::<Vec<Vec<Vec<Id>>>>();
+    if decls.iter().all(|ids| ids.is_empty()) {
+        return stmts;
+    }
+
+    let mut needs_sentinel = vec![];
+    let mut buf = Vec::with_capacity(stmts.len());
+
+    for (idx, stmt) in stmts.into_iter().enumerate() {
+        // Bindings declared by statements after this one.
+        let later = decls[idx + 1..]
+            .iter()
+            .flat_map(|ids| ids.iter().flatten().cloned())
+            .collect::<Vec<_>>();
+
+        let stmt = match stmt.try_into_stmt() {
+            Ok(Stmt::Decl(Decl::Var(mut var))) if var.kind != VarDeclKind::Var => {
+                let len = var.decls.len();
+                var.decls = var
+                    .decls
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, decl)| {
+                        // `let a = a` and `let a = b, b = 1` are also accesses in
+                        // the temporal dead zone.
+                        let mut pending = later.clone();
+                        pending.extend(decls[idx][i..len].iter().flatten().cloned());
+
+                        let init = decl.init.fold_with(&mut TdzChecker {
+                            pending: &pending,
+                            in_fn: false,
+                            locals: vec![],
+                            needs_sentinel: &mut needs_sentinel,
+                        });
+                        VarDeclarator { init, ..decl }
+                    })
+                    .collect();
+
+                // The declaration should overwrite the sentinel.
+                for (i, decl) in var.decls.iter_mut().enumerate() {
+                    if decl.init.is_none()
+                        && decls[idx][i].iter().any(|id| needs_sentinel.contains(id))
+                    {
+                        decl.init = Some(undefined(decl.span()));
+                    }
+                }
+
+                T::from_stmt(Stmt::Decl(Decl::Var(var)))
+            }
+            Ok(stmt) => T::from_stmt(stmt.fold_with(&mut TdzChecker {
+                pending: &later,
+                in_fn: false,
+                locals: vec![],
+                needs_sentinel: &mut needs_sentinel,
+            })),
+            Err(item) => item,
+        };
+
+        buf.push(stmt);
+    }
+
+    if !needs_sentinel.is_empty() {
+        prepend(
+            &mut buf,
+            T::from_stmt(Stmt::Decl(Decl::Var(VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Var,
+                declare: false,
+                decls: needs_sentinel
+                    .into_iter()
+                    .map(|id| VarDeclarator {
+                        span: DUMMY_SP,
+                        name: Pat::Ident(Ident::new(id.0, DUMMY_SP.with_ctxt(id.1))),
+                        init: Some(box helper_expr!(temporal_undefined, "temporalUndefined")),
+                        definite: false,
+                    })
+                    .collect(),
+            }))),
+        );
+    }
+
+    buf
+}
+
+struct TdzChecker<'a> {
+    /// Bindings which are not initialized yet.
+    pending: &'a [Id],
+    /// True if we are in a function which may be called after the
+    /// declaration.
+    in_fn: bool,
+    /// Bindings declared in the enclosing functions.
+    locals: Vec<Id>,
+    needs_sentinel: &'a mut Vec<Id>,
+}
+
+impl TdzChecker<'_> {
+    fn fold_deferred<T>(&mut self, node: T) -> T
+    where
+        T: FoldWith<Self> + for<'any> VisitWith<VarCollector<'any>>,
+    {
+        let old = self.in_fn;
+        let old_len = self.locals.len();
+        self.in_fn = true;
+        node.visit_children(&mut VarCollector {
+            to: &mut self.locals,
+        });
+        let node = node.fold_children(self);
+        self.in_fn = old;
+        self.locals.truncate(old_len);
+        node
+    }
+
+    /// Returns the pending binding `i` refers to.
+    fn pending_binding(&self, i: &Ident) -> Option<Id> {
+        let id = i.to_id();
+        if !self.in_fn {
+            return if self.pending.contains(&id) {
+                Some(id)
+            } else {
+                None
+            };
+        }
+        if self.locals.iter().any(|local| local.0 == i.sym) {
+            return None;
+        }
+
+        // `resolver` resolves a function before the `let` declarations which
+        // follow it, so references from nested functions have their own
+        // context.
+        self.pending.iter().find(|pending| pending.0 == i.sym).cloned()
+    }
+
+    /// Makes `i` refer to the pending binding it resolves to.
+    fn rebind(&self, i: Ident) -> Ident {
+        match self.pending_binding(&i) {
+            Some(id) => Ident {
+                span: i.span.with_ctxt(id.1),
+                ..i
+            },
+            None => i,
+        }
+    }
+
+    fn check(&mut self, i: Ident) -> Expr {
+        let id = match self.pending_binding(&i) {
+            Some(id) => id,
+            None => return Expr::Ident(i),
+        };
+        let i = Ident {
+            span: i.span.with_ctxt(id.1),
+            ..i
+        };
+        let span = i.span;
+        let name = quote_str!(i.sym.clone()).as_arg();
+
+        if self.in_fn {
+            if !self.needs_sentinel.contains(&id) {
+                self.needs_sentinel.push(id);
+            }
+
+            Expr::Call(CallExpr {
+                span,
+                callee: helper!(temporal_ref, "temporalRef"),
+                args: vec![i.as_arg(), name],
+                type_args: Default::default(),
+            })
+        } else {
+            Expr::Call(CallExpr {
+                span,
+                callee: helper!(tdz_error, "tdzError"),
+                args: vec![name],
+                type_args: Default::default(),
+            })
+        }
+    }
+
+    /// Prepends checks for `targets` to `e`, which writes to them.
+    fn check_writes(&mut self, span: Span, targets: Vec<Ident>, e: Expr) -> Expr {
+        let mut exprs = vec![];
+        let mut checked: Vec<Id> = vec![];
+        for i in targets {
+            let id = match self.pending_binding(&i) {
+                Some(id) => id,
+                None => continue,
+            };
+            if checked.contains(&id) {
+                continue;
+            }
+            checked.push(id);
+            exprs.push(box self.check(i));
+        }
+        if exprs.is_empty() {
+            return e;
+        }
+
+        exprs.push(box e);
+        Expr::Seq(SeqExpr { span, exprs })
+    }
+}
+
+macro_rules! deferred {
+    ($T:ty) => {
+        impl Fold<$T> for TdzChecker<'_> {
+            fn fold(&mut self, node: $T) -> $T {
+                self.fold_deferred(node)
+            }
+        }
+    };
+}
+deferred!(Function);
+deferred!(ArrowExpr);
+deferred!(Constructor);
+deferred!(GetterProp);
+deferred!(SetterProp);
+deferred!(ClassProp);
+
+impl Fold<Expr> for TdzChecker<'_> {
+    fn fold(&mut self, e: Expr) -> Expr {
+        match e {
+            Expr::Ident(i) => self.check(i),
+            // Writes throw too, so `x++` becomes `(_tdzError("x"), x++)`.
+            Expr::Update(UpdateExpr {
+                span,
+                op,
+                prefix,
+                arg: box Expr::Ident(i),
+            }) => {
+                let i = self.rebind(i);
+                let targets = vec![i.clone()];
+                let e = Expr::Update(UpdateExpr {
+                    span,
+                    op,
+                    prefix,
+                    arg: box Expr::Ident(i),
+                });
+                self.check_writes(span, targets, e)
+            }
+            Expr::Assign(e) => {
+                let span = e.span;
+                let targets = match e.left {
+                    PatOrExpr::Expr(box Expr::Ident(ref i)) => vec![i.clone()],
+                    PatOrExpr::Pat(ref pat) => find_ids(&**pat),
+                    _ => vec![],
+                };
+                let e = Expr::Assign(e.fold_children(self));
+                self.check_writes(span, targets, e)
+            }
+            Expr::Member(MemberExpr {
+                span,
+                obj,
+                prop,
+                computed,
+            }) => Expr::Member(MemberExpr {
+                span,
+                obj: obj.fold_with(self),
+                prop: if computed { prop.fold_with(self) } else { prop },
+                computed,
+            }),
+            _ => e.fold_children(self),
+        }
+    }
+}
+
+impl Fold<PatOrExpr> for TdzChecker<'_> {
+    fn fold(&mut self, n: PatOrExpr) -> PatOrExpr {
+        match n {
+            // Not a read.
+            PatOrExpr::Expr(box Expr::Ident(i)) => PatOrExpr::Expr(box Expr::Ident(self.rebind(i))),
+            _ => n.fold_children(self),
+        }
+    }
+}
+
+impl Fold<Pat> for TdzChecker<'_> {
+    fn fold(&mut self, p: Pat) -> Pat {
+        match p {
+            Pat::Ident(i) => Pat::Ident(self.rebind(i)),
+            _ => p.fold_children(self),
+        }
+    }
+}
+
+impl Fold<Prop> for TdzChecker<'_> {
+    fn fold(&mut self, p: Prop) -> Prop {
+        match p {
+            Prop::Shorthand(i) => {
+                let value = box self.check(i.clone());
+                Prop::KeyValue(KeyValueProp {
+                    key: PropName::Ident(i),
+                    value,
+                })
+            }
+            _ => p.fold_children(self),
+        }
+    }
+}
+
 fn find_vars<T>(node: &T) -> Vec<Id>
 where
     T: for<'any> VisitWith<VarCollector<'any>>,
@@ -478,13 +813,17 @@ impl Visit<Ident> for InfectionFinder<'_> {
 
 #[cfg(test)]
 mod tests {
-    use super::block_scoping;
-    use crate::compat::es2015::for_of::for_of;
+    use super::{block_scoping, Config};
+    use crate::{compat::es2015::for_of::for_of, resolver};
     use swc_common::chain;
 
+    fn tdz() -> Config {
+        Config { tdz: true }
+    }
+
     test!(
         ::swc_ecma_parser::Syntax::default(),
-        |_| block_scoping(),
+        |_| block_scoping(Default::default()),
         for_loop,
         "for (const key in obj) {
             const bar = obj[key];
@@ -520,7 +859,7 @@ mod tests {
 
     test!(
         ::swc_ecma_parser::Syntax::default(),
-        |_| block_scoping(),
+        |_| block_scoping(Default::default()),
         for_let_loop,
         "let functions = [];
 for (let i = 0; i < 10; i++) {
@@ -545,7 +884,7 @@ functions[7]();
 
     test_exec!(
         ::swc_ecma_parser::Syntax::default(),
-        |_| block_scoping(),
+        |_| block_scoping(Default::default()),
         for_let_loop_exec,
         "let functions = [];
 for (let i = 0; i < 10; i++) {
@@ -560,7 +899,7 @@ expect(functions[7]()).toBe(7);
 
     test_exec!(
         ::swc_ecma_parser::Syntax::default(),
-        |_| block_scoping(),
+        |_| block_scoping(Default::default()),
         for_let_of_exec,
         "let functions = [];
 for (let i of [1, 3, 5, 7, 9]) {
@@ -575,7 +914,10 @@ expect(functions[1]()).toBe(3);
 
     test_exec!(
         ::swc_ecma_parser::Syntax::default(),
-        |_| chain!(for_of(Default::default()), block_scoping()),
+        |_| chain!(
+            for_of(Default::default()),
+            block_scoping(Default::default())
+        ),
         issue_609_1,
         "let functions = [];
 for (let i of [1, 3, 5, 7, 9]) {
@@ -585,6 +927,114 @@ for (let i of [1, 3, 5, 7, 9]) {
 }
 expect(functions[0]()).toBe(1);
 expect(functions[1]()).toBe(3);
+"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| chain!(resolver(), block_scoping(tdz())),
+        tdz_normal_read,
+        "let a = 1;
+function foo() {
+    return a;
+}
+foo(a);",
+        "var a = 1;
+function foo() {
+    return a;
+}
+foo(a);"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| block_scoping(tdz()),
+        tdz_read_before_declaration,
+        "function foo() {
+    return a;
+}
+a;
+let a;",
+        "var a = _temporalUndefined;
+function foo() {
+    return _temporalRef(a, 'a');
+}
+_tdzError('a');
+var a = void 0;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| block_scoping(tdz()),
+        tdz_write_before_declaration,
+        "x = 1;
+x++;
+[x] = [2];
+function foo() {
+    y += 1;
+}
+let x, y;",
+        "var y = _temporalUndefined;
+_tdzError('x'), x = 1;
+_tdzError('x'), x++;
+_tdzError('x'), [x] = [2];
+function foo() {
+    _temporalRef(y, 'y'), y += 1;
+}
+var x, y = void 0;"
+    );
+
+    test_exec!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| chain!(resolver(), block_scoping(tdz())),
+        tdz_write_exec,
+        "expect(() => {
+    a = 1;
+    let a;
+}).toThrow(ReferenceError);
+
+expect(() => {
+    b++;
+    let b = 0;
+}).toThrow(ReferenceError);
+
+function inc() {
+    c += 1;
+}
+expect(inc).toThrow(ReferenceError);
+let c = 1;
+inc();
+expect(c).toBe(2);
+"
+    );
+
+    test_exec!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| chain!(resolver(), block_scoping(tdz())),
+        tdz_same_block_exec,
+        "expect(() => {
+    {
+        a;
+        let a = 1;
+    }
+}).toThrow(ReferenceError);
+
+expect(() => {
+    const b = b + 1;
+}).toThrow(ReferenceError);
+"
+    );
+
+    test_exec!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| block_scoping(tdz()),
+        tdz_closure_exec,
+        "function read() {
+    return a;
+}
+expect(read).toThrow(ReferenceError);
+let a = 2;
+expect(read()).toBe(2);
 "
     );
 }
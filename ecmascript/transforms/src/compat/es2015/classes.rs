@@ -7,12 +7,16 @@ use self::{
     native::is_native,
     prop_name::HashKey,
 };
-use crate::util::{
-    alias_if_required, default_constructor, prepend, prop_name_to_expr, ExprFactory, IsDirective,
-    ModuleItemLike, StmtLike,
+use crate::{
+    pass::Pass,
+    util::{
+        alias_if_required, default_constructor, prepend, prop_name_to_expr, ExprFactory,
+        IsDirective, ModuleItemLike, StmtLike,
+    },
 };
 use ast::*;
 use fxhash::FxBuildHasher;
+use serde::Deserialize;
 use std::iter;
 use swc_common::{Fold, FoldWith, Mark, Spanned, Visit, VisitWith, DUMMY_SP};
 
@@ -25,6 +29,21 @@ mod super_field;
 
 type IndexMap<K, V> = indexmap::IndexMap<K, V, FxBuildHasher>;
 
+pub fn classes(c: Config) -> impl Pass {
+    Classes {
+        config: c,
+        in_strict: false,
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    /// Assigns methods to the prototype instead of defining them with
+    /// `_createClass`, and skips `_classCallCheck`.
+    pub loose: bool,
+}
+
 /// `@babel/plugin-transform-classes`
 ///
 /// # In
@@ -49,6 +68,24 @@ type IndexMap<K, V> = indexmap::IndexMap<K, V, FxBuildHasher>;
 ///     this.name = name;
 ///   }
 ///
+///   _createClass(Test, [{
+///     key: "logger",
+///     value: function logger() {
+///       console.log("Hello", this.name);
+///     }
+///   }]);
+///
+///   return Test;
+/// }();
+/// ```
+///
+/// # Out (loose)
+/// ```js
+/// var Test = function () {
+///   function Test(name) {
+///     this.name = name;
+///   }
+///
 ///   Test.prototype.logger = function logger() {
 ///     console.log("Hello", this.name);
 ///   };
@@ -58,6 +95,7 @@ type IndexMap<K, V> = indexmap::IndexMap<K, V, FxBuildHasher>;
 /// ```
 #[derive(Default, Clone, Copy)]
 pub struct Classes {
+    config: Config,
     in_strict: bool,
 }
 
@@ -387,7 +425,11 @@ impl Classes {
             stmts.push(
                 CallExpr {
                     span: DUMMY_SP,
-                    callee: helper!(inherits, "inherits"),
+                    callee: if self.config.loose {
+                        helper!(inherits_loose, "inheritsLoose")
+                    } else {
+                        helper!(inherits, "inherits")
+                    },
                     args: vec![
                         class_name.clone().as_arg(),
                         super_class_ident.clone().as_arg(),
@@ -528,7 +570,9 @@ impl Classes {
             );
 
             // inject _classCallCheck(this, Bar);
-            inject_class_call_check(&mut body, class_name.clone());
+            if !self.config.loose {
+                inject_class_call_check(&mut body, class_name.clone());
+            }
 
             stmts.push(Stmt::Decl(Decl::Fn(FnDecl {
                 ident: class_name.clone(),
@@ -723,6 +767,8 @@ impl Classes {
         }
 
         let (mut props, mut static_props) = (IndexMap::default(), IndexMap::default());
+        // Methods assigned in loose mode.
+        let mut assignments = vec![];
 
         for m in methods {
             let key = HashKey::from(&m.key);
@@ -784,10 +830,10 @@ impl Classes {
             let value = box Expr::Fn(FnExpr {
                 ident: if m.kind == MethodKind::Method && !computed {
                     match prop_name {
-                        Expr::Ident(ident) => Some(ident),
-                        Expr::Lit(Lit::Str(Str { span, value, .. })) => {
-                            Some(Ident::new(value, span))
-                        }
+                        Expr::Ident(ref ident) => Some(ident.clone()),
+                        Expr::Lit(Lit::Str(Str {
+                            span, ref value, ..
+                        })) => Some(Ident::new(value.clone(), span)),
                         _ => None,
                     }
                 } else {
@@ -796,6 +842,37 @@ impl Classes {
                 function,
             });
 
+            // Accessors still need `Object.defineProperty`.
+            if self.config.loose && m.kind == MethodKind::Method {
+                let obj = if m.is_static {
+                    Expr::Ident(class_name.clone())
+                } else {
+                    class_name.clone().member(quote_ident!("prototype"))
+                };
+                // `[bar]() {}` is lowered to an identifier too.
+                let computed = computed
+                    || match prop_name {
+                        Expr::Ident(..) => false,
+                        _ => true,
+                    };
+
+                assignments.push(
+                    AssignExpr {
+                        span: DUMMY_SP,
+                        op: op!("="),
+                        left: PatOrExpr::Expr(box Expr::Member(MemberExpr {
+                            span: DUMMY_SP,
+                            obj: obj.as_obj(),
+                            prop: box prop_name,
+                            computed,
+                        })),
+                        right: value,
+                    }
+                    .into_stmt(),
+                );
+                continue;
+            }
+
             let data = append_to.entry(key).or_insert_with(|| Data {
                 key_prop,
                 get: None,
@@ -810,9 +887,9 @@ impl Classes {
         }
 
         if props.is_empty() && static_props.is_empty() {
-            return vec![];
+            return assignments;
         }
-        vec![mk_create_class_call(
+        assignments.push(mk_create_class_call(
             class_name,
             mk_arg_obj_for_create_class(props),
             if static_props.is_empty() {
@@ -820,7 +897,8 @@ impl Classes {
             } else {
                 Some(mk_arg_obj_for_create_class(static_props))
             },
-        )]
+        ));
+        assignments
     }
 }
 
@@ -3,6 +3,7 @@ use crate::{
     util::{ExprFactory, StmtLike},
 };
 use ast::*;
+use serde::Deserialize;
 use swc_common::{Fold, FoldWith, Mark, Spanned, Visit, VisitWith, DUMMY_SP};
 
 /// `@babel/plugin-transform-computed-properties`
@@ -34,18 +35,26 @@ use swc_common::{Fold, FoldWith, Mark, Spanned, Visit, VisitWith, DUMMY_SP};
 /// );
 /// ```
 ///
-/// TODO(kdy1): cache reference like (_f = f, mutatorMap[_f].get = function(){})
-///     instead of (mutatorMap[f].get = function(){}
-pub fn computed_properties() -> impl Pass {
-    ComputedProps
+/// Accessors are defined with `Object.defineProperty` in source order.
+pub fn computed_properties(c: Config) -> impl Pass {
+    ComputedProps { c }
 }
 
-struct ComputedProps;
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    /// Use assignments instead of `_defineProperty` for data properties.
+    #[serde(default)]
+    pub loose: bool,
+}
+
+struct ComputedProps {
+    c: Config,
+}
 
-#[derive(Default)]
 struct ObjectLitFolder {
+    c: Config,
     vars: Vec<VarDeclarator>,
-    used_define_enum_props: bool,
 }
 
 impl Fold<Expr> for ObjectLitFolder {
@@ -63,7 +72,6 @@ impl Fold<Expr> for ObjectLitFolder {
                 let obj_ident = quote_ident!(span.apply_mark(mark), "_obj");
 
                 let mut exprs = Vec::with_capacity(props.len() + 2);
-                let mutator_map = quote_ident!(span.apply_mark(mark), "_mutatorMap");
 
                 // Optimization
                 let obj_props = {
@@ -72,9 +80,11 @@ impl Fold<Expr> for ObjectLitFolder {
                     props.drain(0..idx).collect()
                 };
 
-                let props_cnt = props.len();
+                // `_defineProperty` and `_defineAccessor` return the object, so we can
+                // skip the temporary variable if there's only one property to define.
+                let is_single = props.len() == 1 && !self.c.loose;
 
-                exprs.push(if props_cnt == 1 {
+                exprs.push(if is_single {
                     box Expr::Object(ObjectLit {
                         span: DUMMY_SP,
                         props: obj_props,
@@ -93,6 +103,11 @@ impl Fold<Expr> for ObjectLitFolder {
 
                 for prop in props {
                     let span = prop.span();
+                    let obj = if is_single {
+                        exprs.pop().unwrap()
+                    } else {
+                        box Expr::Ident(obj_ident.clone())
+                    };
 
                     let (key, value) = match prop {
                         PropOrSpread::Prop(box prop) => match prop {
@@ -105,97 +120,62 @@ impl Fold<Expr> for ObjectLitFolder {
                                 Expr::Ident(ident),
                             ),
                             Prop::KeyValue(KeyValueProp { key, value }) => {
+                                // `__proto__: v` sets the prototype instead of defining a
+                                // property.
+                                if is_proto_key(&key) {
+                                    exprs.push(box Expr::Assign(AssignExpr {
+                                        span,
+                                        left: PatOrExpr::Expr(
+                                            box obj.member(quote_ident!("__proto__")),
+                                        ),
+                                        op: op!("="),
+                                        right: value,
+                                    }));
+                                    continue;
+                                }
+
                                 (prop_name_to_expr(key), *value)
                             }
                             Prop::Assign(..) => {
                                 unreachable!("assign property in object literal is invalid")
                             }
-                            prop @ Prop::Getter(GetterProp { .. })
-                            | prop @ Prop::Setter(SetterProp { .. }) => {
-                                self.used_define_enum_props = true;
-
-                                // getter/setter property name
-                                let gs_prop_name = match prop {
-                                    Prop::Getter(..) => Some("get"),
-                                    Prop::Setter(..) => Some("set"),
-                                    _ => None,
-                                };
-                                let (key, function) = match prop {
-                                    Prop::Getter(GetterProp {
-                                        span,
-                                        body,
-                                        key,
-                                        type_ann,
-                                    }) => (
-                                        key,
-                                        Function {
-                                            span,
-                                            body,
-                                            is_async: false,
-                                            is_generator: false,
-                                            params: vec![],
-                                            decorators: Default::default(),
-                                            type_params: Default::default(),
-                                            return_type: type_ann,
-                                        },
-                                    ),
-                                    Prop::Setter(SetterProp {
-                                        span,
-                                        body,
-                                        param,
-                                        key,
-                                    }) => (
-                                        key,
-                                        Function {
-                                            span,
-                                            body,
-                                            is_async: false,
-                                            is_generator: false,
-                                            params: vec![param],
-                                            decorators: Default::default(),
-                                            type_params: Default::default(),
-                                            return_type: Default::default(),
-                                        },
-                                    ),
-                                    _ => unreachable!(),
-                                };
-
-                                // mutator[f]
-                                let mutator_elem =
-                                    mutator_map.clone().computed_member(prop_name_to_expr(key));
-
-                                // mutator[f] = mutator[f] || {}
-                                exprs.push(box Expr::Assign(AssignExpr {
+                            Prop::Getter(GetterProp {
+                                span,
+                                body,
+                                key,
+                                type_ann,
+                            }) => {
+                                let function = Function {
                                     span,
-                                    left: PatOrExpr::Expr(box mutator_elem.clone()),
-                                    op: op!("="),
-                                    right: box Expr::Bin(BinExpr {
-                                        span,
-                                        left: box mutator_elem.clone(),
-                                        op: op!("||"),
-                                        right: box Expr::Object(ObjectLit {
-                                            span,
-                                            props: vec![],
-                                        }),
-                                    }),
-                                }));
-
-                                // mutator[f].get = function(){}
-                                exprs.push(box Expr::Assign(AssignExpr {
+                                    body,
+                                    is_async: false,
+                                    is_generator: false,
+                                    params: vec![],
+                                    decorators: Default::default(),
+                                    type_params: Default::default(),
+                                    return_type: type_ann,
+                                };
+                                exprs.push(define_accessor("get", obj, key, function));
+                                continue;
+                            }
+                            Prop::Setter(SetterProp {
+                                span,
+                                body,
+                                param,
+                                key,
+                            }) => {
+                                let function = Function {
                                     span,
-                                    left: PatOrExpr::Expr(
-                                        box mutator_elem
-                                            .member(quote_ident!(gs_prop_name.unwrap())),
-                                    ),
-                                    op: op!("="),
-                                    right: box Expr::Fn(FnExpr {
-                                        ident: None,
-                                        function,
-                                    }),
-                                }));
-
+                                    body,
+                                    is_async: false,
+                                    is_generator: false,
+                                    params: vec![param],
+                                    decorators: Default::default(),
+                                    type_params: Default::default(),
+                                    return_type: Default::default(),
+                                };
+                                exprs.push(define_accessor("set", obj, key, function));
                                 continue;
-                                // unimplemented!("getter /setter property")
                             }
                             Prop::Method(MethodProp { key, function }) => (
                                 prop_name_to_expr(key),
@@ -208,45 +188,36 @@ impl Fold<Expr> for ObjectLitFolder {
                         PropOrSpread::Spread(..) => unimplemented!("computed spread property"),
                     };
 
-                    if props_cnt == 1 {
-                        return Expr::Call(CallExpr {
+                    // Assigning to `__proto__` would change the prototype, so we always
+                    // define it.
+                    if self.c.loose && !is_proto_str(&key) {
+                        exprs.push(box Expr::Assign(AssignExpr {
                             span,
-                            callee: helper!(define_property, "defineProperty"),
-                            args: vec![exprs.pop().unwrap().as_arg(), key.as_arg(), value.as_arg()],
-                            type_args: Default::default(),
-                        });
+                            left: PatOrExpr::Expr(box obj.computed_member(key)),
+                            op: op!("="),
+                            right: box value,
+                        }));
+                        continue;
                     }
+
                     exprs.push(box Expr::Call(CallExpr {
                         span,
                         callee: helper!(define_property, "defineProperty"),
-                        args: vec![obj_ident.clone().as_arg(), key.as_arg(), value.as_arg()],
+                        args: vec![obj.as_arg(), key.as_arg(), value.as_arg()],
                         type_args: Default::default(),
                     }));
                 }
 
+                if is_single {
+                    return *exprs.pop().unwrap();
+                }
+
                 self.vars.push(VarDeclarator {
                     span,
                     name: Pat::Ident(obj_ident.clone()),
                     init: None,
                     definite: false,
                 });
-                if self.used_define_enum_props {
-                    self.vars.push(VarDeclarator {
-                        span: DUMMY_SP,
-                        name: Pat::Ident(mutator_map.clone()),
-                        init: Some(box Expr::Object(ObjectLit {
-                            span: DUMMY_SP,
-                            props: vec![],
-                        })),
-                        definite: false,
-                    });
-                    exprs.push(box Expr::Call(CallExpr {
-                        span,
-                        callee: helper!(define_enumerable_properties, "defineEnumerableProperties"),
-                        args: vec![obj_ident.clone().as_arg(), mutator_map.as_arg()],
-                        type_args: Default::default(),
-                    }));
-                }
 
                 // Last value
                 exprs.push(box Expr::Ident(obj_ident));
@@ -260,6 +231,43 @@ impl Fold<Expr> for ObjectLitFolder {
     }
 }
 
+/// Creates `_defineAccessor(type, obj, key, function () {})`.
+fn define_accessor(type_: &str, obj: Box<Expr>, key: PropName, function: Function) -> Box<Expr> {
+    let span = function.span;
+
+    box Expr::Call(CallExpr {
+        span,
+        callee: helper!(define_accessor, "defineAccessor"),
+        args: vec![
+            Lit::Str(quote_str!(type_)).as_arg(),
+            obj.as_arg(),
+            prop_name_to_expr(key).as_arg(),
+            FnExpr {
+                ident: None,
+                function,
+            }
+            .as_arg(),
+        ],
+        type_args: Default::default(),
+    })
+}
+
+/// Returns true for a non-computed `__proto__` key.
+fn is_proto_key(key: &PropName) -> bool {
+    match *key {
+        PropName::Ident(Ident { ref sym, .. }) => *sym == *"__proto__",
+        PropName::Str(Str { ref value, .. }) => *value == *"__proto__",
+        _ => false,
+    }
+}
+
+fn is_proto_str(key: &Expr) -> bool {
+    match *key {
+        Expr::Lit(Lit::Str(Str { ref value, .. })) => *value == *"__proto__",
+        _ => false,
+    }
+}
+
 fn is_complex<T: VisitWith<ComplexVisitor>>(node: &T) -> bool {
     let mut visitor = ComplexVisitor::default();
     node.visit_children(&mut visitor);
@@ -299,7 +307,10 @@ where
                 continue;
             }
 
-            let mut folder = ObjectLitFolder::default();
+            let mut folder = ObjectLitFolder {
+                c: self.c,
+                vars: vec![],
+            };
             let stmt = stmt.fold_with(&mut folder);
 
             // Add variable declaration
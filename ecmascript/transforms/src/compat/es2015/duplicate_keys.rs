@@ -1,6 +1,6 @@
 use crate::pass::Pass;
 use ast::*;
-use hashbrown::HashSet;
+use hashbrown::HashMap;
 use swc_atoms::JsWord;
 use swc_common::{Fold, FoldWith, Spanned};
 
@@ -31,72 +31,60 @@ impl Fold<Expr> for DuplicateKeys {
 
 #[derive(Default)]
 struct PropFolder {
-    getter_props: HashSet<JsWord>,
-    setter_props: HashSet<JsWord>,
+    /// Kinds of properties seen so far, by key.
+    keys: HashMap<JsWord, Kinds>,
 }
 
-impl Fold<Expr> for PropFolder {
-    fn fold(&mut self, node: Expr) -> Expr {
-        node
-    }
+#[derive(Default)]
+struct Kinds {
+    init: bool,
+    get: bool,
+    set: bool,
 }
 
-impl Fold<Prop> for PropFolder {
-    fn fold(&mut self, prop: Prop) -> Prop {
-        match prop {
-            Prop::Shorthand(ident) => {
-                //
-                if !self.getter_props.insert(ident.sym.clone())
-                    || !self.setter_props.insert(ident.sym.clone())
-                {
-                    Prop::KeyValue(KeyValueProp {
-                        key: PropName::Computed(ComputedPropName {
-                            span: ident.span,
-                            expr: box Expr::Lit(Lit::Str(quote_str!(ident.sym.clone()))),
-                        }),
-                        value: box Expr::Ident(ident),
-                    })
-                } else {
-                    Prop::Shorthand(ident)
-                }
-            }
+#[derive(Clone, Copy)]
+enum Kind {
+    Init,
+    Get,
+    Set,
+}
 
-            Prop::Assign(..) => unreachable!("assign property in object literal is invalid"),
+impl PropFolder {
+    /// Records a property and returns true if it conflicts with a previous
+    /// one.
+    ///
+    /// A getter and a setter with the same key form a pair, and are not
+    /// conflicts.
+    fn record(&mut self, key: &JsWord, kind: Kind) -> bool {
+        let prev = self.keys.entry(key.clone()).or_default();
 
-            Prop::Getter(..) => prop.fold_children(&mut PropNameFolder {
-                props: &mut self.getter_props,
-            }),
-            Prop::Setter(..) => prop.fold_children(&mut PropNameFolder {
-                props: &mut self.setter_props,
-            }),
-            _ => {
-                let prop = prop.fold_children(&mut PropNameFolder {
-                    props: &mut self.getter_props,
-                });
-                prop.fold_children(&mut PropNameFolder {
-                    props: &mut self.setter_props,
-                })
+        match kind {
+            Kind::Init => {
+                let conflict = prev.init || prev.get || prev.set;
+                prev.init = true;
+                conflict
+            }
+            Kind::Get => {
+                let conflict = prev.init || prev.get;
+                prev.get = true;
+                conflict
+            }
+            Kind::Set => {
+                let conflict = prev.init || prev.set;
+                prev.set = true;
+                conflict
             }
         }
     }
-}
-
-struct PropNameFolder<'a> {
-    props: &'a mut HashSet<JsWord>,
-}
-impl<'a> Fold<Expr> for PropNameFolder<'a> {
-    fn fold(&mut self, node: Expr) -> Expr {
-        node
-    }
-}
 
-impl<'a> Fold<PropName> for PropNameFolder<'a> {
-    fn fold(&mut self, name: PropName) -> PropName {
-        let span = name.span();
+    /// Converts `key` to a computed key if it conflicts with a previous
+    /// property.
+    fn fold_key(&mut self, key: PropName, kind: Kind) -> PropName {
+        let span = key.span();
 
-        match name {
+        match key {
             PropName::Ident(ident) => {
-                if !self.props.insert(ident.sym.clone()) {
+                if self.record(&ident.sym, kind) {
                     PropName::Computed(ComputedPropName {
                         span,
                         expr: box Expr::Lit(Lit::Str(Str {
@@ -110,7 +98,7 @@ impl<'a> Fold<PropName> for PropNameFolder<'a> {
                 }
             }
             PropName::Str(s) => {
-                if !self.props.insert(s.value.clone()) {
+                if self.record(&s.value, kind) {
                     PropName::Computed(ComputedPropName {
                         span: s.span,
                         expr: box Expr::Lit(Lit::Str(s)),
@@ -123,13 +111,73 @@ impl<'a> Fold<PropName> for PropNameFolder<'a> {
                 // Computed property might collide
                 match *expr {
                     Expr::Lit(Lit::Str(Str { ref value, .. })) => {
-                        self.props.insert(value.clone());
+                        self.record(value, kind);
                     }
                     _ => {}
                 }
                 PropName::Computed(ComputedPropName { span, expr })
             }
-            _ => name,
+            _ => key,
+        }
+    }
+}
+
+impl Fold<Expr> for PropFolder {
+    fn fold(&mut self, node: Expr) -> Expr {
+        node
+    }
+}
+
+impl Fold<Prop> for PropFolder {
+    fn fold(&mut self, prop: Prop) -> Prop {
+        match prop {
+            Prop::Shorthand(ident) => {
+                //
+                if self.record(&ident.sym, Kind::Init) {
+                    Prop::KeyValue(KeyValueProp {
+                        key: PropName::Computed(ComputedPropName {
+                            span: ident.span,
+                            expr: box Expr::Lit(Lit::Str(quote_str!(ident.sym.clone()))),
+                        }),
+                        value: box Expr::Ident(ident),
+                    })
+                } else {
+                    Prop::Shorthand(ident)
+                }
+            }
+
+            Prop::Assign(..) => unreachable!("assign property in object literal is invalid"),
+
+            Prop::KeyValue(KeyValueProp { key, value }) => Prop::KeyValue(KeyValueProp {
+                key: self.fold_key(key, Kind::Init),
+                value,
+            }),
+            Prop::Method(MethodProp { key, function }) => Prop::Method(MethodProp {
+                key: self.fold_key(key, Kind::Init),
+                function,
+            }),
+            Prop::Getter(GetterProp {
+                span,
+                key,
+                type_ann,
+                body,
+            }) => Prop::Getter(GetterProp {
+                span,
+                key: self.fold_key(key, Kind::Get),
+                type_ann,
+                body,
+            }),
+            Prop::Setter(SetterProp {
+                span,
+                key,
+                param,
+                body,
+            }) => Prop::Setter(SetterProp {
+                span,
+                key: self.fold_key(key, Kind::Set),
+                param,
+                body,
+            }),
         }
     }
 }
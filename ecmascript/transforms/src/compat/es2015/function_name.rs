@@ -1,6 +1,10 @@
-use crate::{pass::Pass, util::UsageFinder};
+use crate::{
+    pass::Pass,
+    util::{is_valid_ident, UsageFinder},
+};
 use ast::*;
-use swc_common::{Fold, FoldWith};
+use swc_atoms::JsWord;
+use swc_common::{Fold, FoldWith, SyntaxContext, Visit, VisitWith};
 
 /// `@babel/plugin-transform-function-name`
 ///
@@ -11,6 +15,9 @@ use swc_common::{Fold, FoldWith};
 ///   return x;
 /// };
 /// var Foo = (class {});
+/// var obj = {
+///   foo: function () {}
+/// };
 /// ```
 /// ## Out
 /// ```js
@@ -18,6 +25,9 @@ use swc_common::{Fold, FoldWith};
 ///   return x;
 /// }
 /// var Foo = (class Foo {});
+/// var obj = {
+///   foo: function foo() {}
+/// };
 /// ```
 pub fn function_name() -> impl Pass {
     FnName
@@ -28,6 +38,26 @@ struct FnName;
 
 struct Renamer {
     name: Option<Ident>,
+    /// Property keys are not bindings, so any reference to a variable with the
+    /// same name would be shadowed by the function name.
+    is_key: bool,
+}
+
+impl Renamer {
+    fn for_key(key: Ident) -> Self {
+        Renamer {
+            name: Some(prepare(key, false)),
+            is_key: true,
+        }
+    }
+
+    /// Anonymous default exports are named `_default`.
+    fn for_default() -> Self {
+        Renamer {
+            name: Some(private_ident!("_default")),
+            is_key: false,
+        }
+    }
 }
 
 /// This function makes a new private identifier if required.
@@ -45,24 +75,36 @@ fn prepare(i: Ident, force: bool) -> Ident {
 
 impl Fold<KeyValueProp> for FnName {
     fn fold(&mut self, p: KeyValueProp) -> KeyValueProp {
-        let mut p = p.fold_children(self);
-
-        p.value = match *p.value {
-            Expr::Fn(expr @ FnExpr { ident: None, .. }) => {
-                //
-                if let PropName::Ident(ref i) = p.key {
-                    box Expr::Fn(FnExpr {
-                        ident: Some(prepare(i.clone(), false)),
-                        ..expr
-                    })
-                } else {
-                    box Expr::Fn(expr)
-                }
+        let p = p.fold_children(self);
+
+        let name = match p.key {
+            PropName::Ident(ref i) => i.clone(),
+            PropName::Str(ref s) if is_valid_ident(&s.value) => Ident::new(s.value.clone(), s.span),
+            _ => return p,
+        };
+        let value = p.value.fold_with(&mut Renamer::for_key(name));
+
+        KeyValueProp { value, ..p }
+    }
+}
+
+impl Fold<ClassProp> for FnName {
+    fn fold(&mut self, p: ClassProp) -> ClassProp {
+        let p = p.fold_children(self);
+
+        if p.computed {
+            return p;
+        }
+        let name = match *p.key {
+            Expr::Ident(ref i) => i.clone(),
+            Expr::Lit(Lit::Str(ref s)) if is_valid_ident(&s.value) => {
+                Ident::new(s.value.clone(), s.span)
             }
-            _ => p.value,
+            _ => return p,
         };
+        let value = p.value.fold_with(&mut Renamer::for_key(name));
 
-        p
+        ClassProp { value, ..p }
     }
 }
 
@@ -74,6 +116,7 @@ impl Fold<VarDeclarator> for FnName {
             Pat::Ident(ref mut ident) => {
                 let mut folder = Renamer {
                     name: Some(prepare(ident.clone(), false)),
+                    is_key: false,
                 };
                 let init = decl.init.fold_with(&mut folder);
 
@@ -97,6 +140,7 @@ impl Fold<AssignExpr> for FnName {
             | PatOrExpr::Expr(box Expr::Ident(ref mut ident)) => {
                 let mut folder = Renamer {
                     name: Some(ident.clone()),
+                    is_key: false,
                 };
 
                 let right = expr.right.fold_with(&mut folder);
@@ -108,6 +152,32 @@ impl Fold<AssignExpr> for FnName {
     }
 }
 
+impl Fold<ExportDefaultDecl> for FnName {
+    fn fold(&mut self, export: ExportDefaultDecl) -> ExportDefaultDecl {
+        let export = export.fold_children(self);
+
+        let decl = match export.decl {
+            DefaultDecl::Fn(f) => DefaultDecl::Fn(f.fold_with(&mut Renamer::for_default())),
+            DefaultDecl::Class(c) => {
+                DefaultDecl::Class(c.fold_with(&mut Renamer::for_default()))
+            }
+            _ => export.decl,
+        };
+
+        ExportDefaultDecl { decl, ..export }
+    }
+}
+
+impl Fold<ExportDefaultExpr> for FnName {
+    fn fold(&mut self, export: ExportDefaultExpr) -> ExportDefaultExpr {
+        let export = export.fold_children(self);
+
+        let expr = export.expr.fold_with(&mut Renamer::for_default());
+
+        ExportDefaultExpr { expr, ..export }
+    }
+}
+
 macro_rules! impl_for {
     ($T:tt) => {
         impl Fold<$T> for Renamer {
@@ -127,7 +197,9 @@ macro_rules! impl_for {
                         };
                         // If function's body references the name of variable, we just skip the
                         // function
-                        if UsageFinder::find(&name, &node) {
+                        if UsageFinder::find(&name, &node)
+                            || (self.is_key && references_sym(&name.sym, &node))
+                        {
                             // self.name = Some(name);
                             $T {
                                 ident: None,
@@ -148,6 +220,97 @@ macro_rules! impl_for {
 impl_for!(FnExpr);
 impl_for!(ClassExpr);
 
+/// Returns true if `node` references a variable named `sym` which is not
+/// declared in `node`.
+fn references_sym<N>(sym: &JsWord, node: &N) -> bool
+where
+    N: VisitWith<SymFinder>,
+{
+    let mut v = SymFinder {
+        sym: sym.clone(),
+        declared: vec![],
+        used: vec![],
+    };
+    node.visit_with(&mut v);
+    v.used.iter().any(|ctxt| !v.declared.contains(ctxt))
+}
+
+struct SymFinder {
+    sym: JsWord,
+    declared: Vec<SyntaxContext>,
+    used: Vec<SyntaxContext>,
+}
+
+impl SymFinder {
+    fn add(&mut self, i: &Ident, declared: bool) {
+        if i.sym == self.sym {
+            if declared {
+                self.declared.push(i.span.ctxt());
+            } else {
+                self.used.push(i.span.ctxt());
+            }
+        }
+    }
+}
+
+impl Visit<Pat> for SymFinder {
+    fn visit(&mut self, p: &Pat) {
+        match *p {
+            Pat::Ident(ref i) => self.add(i, true),
+            _ => p.visit_children(self),
+        }
+    }
+}
+
+impl Visit<AssignPatProp> for SymFinder {
+    fn visit(&mut self, p: &AssignPatProp) {
+        self.add(&p.key, true);
+        p.value.visit_with(self);
+    }
+}
+
+impl Visit<FnDecl> for SymFinder {
+    fn visit(&mut self, f: &FnDecl) {
+        self.add(&f.ident, true);
+        f.function.visit_with(self);
+    }
+}
+
+impl Visit<ClassDecl> for SymFinder {
+    fn visit(&mut self, c: &ClassDecl) {
+        self.add(&c.ident, true);
+        c.class.visit_with(self);
+    }
+}
+
+impl Visit<Expr> for SymFinder {
+    fn visit(&mut self, e: &Expr) {
+        match *e {
+            Expr::Ident(ref i) => self.add(i, false),
+            _ => e.visit_children(self),
+        }
+    }
+}
+
+impl Visit<Prop> for SymFinder {
+    fn visit(&mut self, p: &Prop) {
+        match *p {
+            Prop::Shorthand(ref i) => self.add(i, false),
+            _ => p.visit_children(self),
+        }
+    }
+}
+
+impl Visit<MemberExpr> for SymFinder {
+    fn visit(&mut self, e: &MemberExpr) {
+        e.obj.visit_with(self);
+
+        if e.computed {
+            e.prop.visit_with(self);
+        }
+    }
+}
+
 macro_rules! noop {
     ($T:tt) => {
         impl Fold<$T> for Renamer {
@@ -1,10 +1,21 @@
-use crate::util::ExprFactory;
+use crate::{
+    helpers::HELPERS,
+    pass::Pass,
+    util::{ident::IdentLike, ExprFactory, Id},
+};
 use ast::*;
-use swc_common::{Fold, FoldWith, Visit, VisitWith};
+use fxhash::FxHashSet;
+use serde::Deserialize;
+use swc_common::{Fold, FoldWith, Span, Visit, VisitWith};
 
 /// `@babel/plugin-transform-instanceof`
 ///
-///
+/// Right-hand sides which are known not to have a custom `Symbol.hasInstance`
+/// (`Array`, `Date`, `Error`, `Function`, `Object`, `RegExp` and
+/// `this.constructor`) are not wrapped unless
+/// `wrap_known_constructors` is set. The global constructors are only
+/// skipped if they are not shadowed by a binding of the program, so
+/// `resolver` should run before this pass.
 ///
 /// # Example
 ///
@@ -27,8 +38,69 @@ use swc_common::{Fold, FoldWith, Visit, VisitWith};
 ///
 /// _instanceof(foo, Bar);
 /// ```
-#[derive(Clone)]
-pub struct InstanceOf;
+pub fn instance_of(c: Config) -> impl Pass {
+    InstanceOf {
+        config: c,
+        declared: Default::default(),
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    /// Wraps `instanceof` even if the right-hand side is a known constructor.
+    pub wrap_known_constructors: bool,
+}
+
+#[derive(Default, Clone)]
+pub struct InstanceOf {
+    config: Config,
+    /// Bindings declared in the program.
+    declared: FxHashSet<Id>,
+}
+
+impl InstanceOf {
+    fn fold_program<T>(&mut self, node: T) -> T
+    where
+        T: FoldWith<Self> + for<'any> VisitWith<BindingCollector<'any>>,
+    {
+        node.visit_with(&mut BindingCollector {
+            declared: &mut self.declared,
+        });
+
+        node.fold_children(self)
+    }
+
+    /// Returns true if `right` is a global constructor without a custom
+    /// `Symbol.hasInstance` or `this.constructor`.
+    fn is_known_constructor(&self, right: &Expr) -> bool {
+        match *right {
+            Expr::Ident(ref i) if !self.declared.contains(&i.to_id()) => match &*i.sym {
+                "Array" | "Date" | "Error" | "Function" | "Object" | "RegExp" => true,
+                _ => false,
+            },
+            Expr::Member(MemberExpr {
+                obj: ExprOrSuper::Expr(box Expr::This(..)),
+                prop: box Expr::Ident(ref prop),
+                computed: false,
+                ..
+            }) => prop.sym == *"constructor",
+            _ => false,
+        }
+    }
+}
+
+impl Fold<Module> for InstanceOf {
+    fn fold(&mut self, m: Module) -> Module {
+        self.fold_program(m)
+    }
+}
+
+impl Fold<Script> for InstanceOf {
+    fn fold(&mut self, s: Script) -> Script {
+        self.fold_program(s)
+    }
+}
 
 impl Fold<Expr> for InstanceOf {
     fn fold(&mut self, expr: Expr) -> Expr {
@@ -60,13 +132,206 @@ impl Fold<Expr> for InstanceOf {
                 left,
                 op: op!("instanceof"),
                 right,
-            }) => Expr::Call(CallExpr {
-                span,
-                callee: helper!(span, instanceof, "instanceof"),
-                args: vec![left.as_arg(), right.as_arg()],
-                type_args: Default::default(),
-            }),
+            }) => {
+                if is_helper_code(span)
+                    || (!self.config.wrap_known_constructors && self.is_known_constructor(&right))
+                {
+                    return Expr::Bin(BinExpr {
+                        span,
+                        left,
+                        op: op!("instanceof"),
+                        right,
+                    });
+                }
+
+                Expr::Call(CallExpr {
+                    span,
+                    callee: helper!(span, instanceof, "instanceof"),
+                    args: vec![left.as_arg(), right.as_arg()],
+                    type_args: Default::default(),
+                })
+            }
             _ => expr,
         }
     }
 }
+
+/// Collects all bindings, including ones in nested scopes.
+struct BindingCollector<'a> {
+    declared: &'a mut FxHashSet<Id>,
+}
+
+impl BindingCollector<'_> {
+    fn add(&mut self, i: &Ident) {
+        self.declared.insert(i.to_id());
+    }
+}
+
+impl Visit<Pat> for BindingCollector<'_> {
+    fn visit(&mut self, p: &Pat) {
+        match *p {
+            Pat::Ident(ref i) => self.add(i),
+            _ => p.visit_children(self),
+        }
+    }
+}
+
+impl Visit<AssignPatProp> for BindingCollector<'_> {
+    fn visit(&mut self, p: &AssignPatProp) {
+        self.add(&p.key);
+        p.value.visit_with(self);
+    }
+}
+
+impl Visit<FnDecl> for BindingCollector<'_> {
+    fn visit(&mut self, f: &FnDecl) {
+        self.add(&f.ident);
+        f.function.visit_with(self);
+    }
+}
+
+impl Visit<ClassDecl> for BindingCollector<'_> {
+    fn visit(&mut self, c: &ClassDecl) {
+        self.add(&c.ident);
+        c.class.visit_with(self);
+    }
+}
+
+impl Visit<FnExpr> for BindingCollector<'_> {
+    fn visit(&mut self, f: &FnExpr) {
+        if let Some(ref i) = f.ident {
+            self.add(i);
+        }
+        f.function.visit_with(self);
+    }
+}
+
+impl Visit<ClassExpr> for BindingCollector<'_> {
+    fn visit(&mut self, c: &ClassExpr) {
+        if let Some(ref i) = c.ident {
+            self.add(i);
+        }
+        c.class.visit_with(self);
+    }
+}
+
+impl Visit<ImportSpecifier> for BindingCollector<'_> {
+    fn visit(&mut self, s: &ImportSpecifier) {
+        match *s {
+            ImportSpecifier::Specific(ImportSpecific { ref local, .. })
+            | ImportSpecifier::Default(ImportDefault { ref local, .. })
+            | ImportSpecifier::Namespace(ImportStarAs { ref local, .. }) => self.add(local),
+        }
+    }
+}
+
+/// The injected `_instanceof` helper uses `instanceof` itself, so it must not
+/// be wrapped if this pass runs again after helpers are injected.
+fn is_helper_code(span: Span) -> bool {
+    HELPERS.is_set() && HELPERS.with(|helpers| span.ctxt().outer() == helpers.mark())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{helpers::InjectHelpers, resolver};
+    use swc_common::chain;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| instance_of(Default::default()),
+        custom_class,
+        "x instanceof MyClass;",
+        "_instanceof(x, MyClass);"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| instance_of(Default::default()),
+        known_constructors,
+        "x instanceof Array;
+x instanceof Error;
+x instanceof this.constructor;
+x instanceof this[constructor];",
+        "x instanceof Array;
+x instanceof Error;
+x instanceof this.constructor;
+_instanceof(x, this[constructor]);"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| instance_of(Config {
+            wrap_known_constructors: true
+        }),
+        wrap_known_constructors,
+        "x instanceof Array;
+x instanceof this.constructor;",
+        "_instanceof(x, Array);
+_instanceof(x, this.constructor);"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| chain!(
+            instance_of(Default::default()),
+            InjectHelpers,
+            instance_of(Default::default())
+        ),
+        idempotent,
+        "x instanceof MyClass;",
+        "function _instanceof(left, right) {
+  if (right != null && typeof Symbol !== \"undefined\" && right[Symbol.hasInstance]) {
+    return right[Symbol.hasInstance](left);
+  } else {
+    return left instanceof right;
+  }
+}
+_instanceof(x, MyClass);"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| chain!(resolver(), instance_of(Default::default())),
+        shadowed_known_constructors,
+        "function f(Array) {
+    return x instanceof Array;
+}
+const Error = MyError;
+x instanceof Error;
+x instanceof Object;",
+        "function f(Array1) {
+    return _instanceof(x, Array1);
+}
+const Error1 = MyError;
+_instanceof(x, Error1);
+x instanceof Object;"
+    );
+
+    test_exec!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| chain!(resolver(), instance_of(Default::default())),
+        exec_shadowed,
+        "function test(Array) {
+    return [] instanceof Array;
+}
+
+expect(test({ [Symbol.hasInstance]: () => false })).toBe(false);
+expect([] instanceof Array).toBe(true);"
+    );
+
+    test_exec!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| instance_of(Default::default()),
+        exec,
+        "class Even {
+    static [Symbol.hasInstance](n) {
+        return n % 2 === 0;
+    }
+}
+
+expect(2 instanceof Even).toBe(true);
+expect(1 instanceof Even).toBe(false);
+expect([] instanceof Array).toBe(true);"
+    );
+}
@@ -0,0 +1,315 @@
+use crate::{
+    pass::Pass,
+    util::{undefined, ExprFactory},
+};
+use ast::*;
+use std::mem::replace;
+use swc_common::{Fold, FoldWith, Spanned, Visit, VisitWith, DUMMY_SP};
+
+/// `@babel/plugin-transform-new-target`
+///
+/// This pass should run before `arrow` and `Classes`. `new.target` in an
+/// arrow function is lowered using `this`, which is then captured by `arrow`,
+/// and `Classes` turns `this.constructor` in a constructor into the class
+/// being constructed.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// function Foo() {
+///   const bar = () => new.target;
+/// }
+///
+/// class Bar {
+///   constructor() {
+///     new.target;
+///   }
+///
+///   method() {
+///     new.target;
+///   }
+/// }
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// function Foo() {
+///   const bar = () => this instanceof Foo ? this.constructor : void 0;
+/// }
+///
+/// class Bar {
+///   constructor() {
+///     this.constructor;
+///   }
+///
+///   method() {
+///     void 0;
+///   }
+/// }
+/// ```
+pub fn new_target() -> impl Pass {
+    NewTarget { ctx: Ctx::Top }
+}
+
+struct NewTarget {
+    ctx: Ctx,
+}
+
+enum Ctx {
+    /// `new.target` is a syntax error here, so it's left as is.
+    Top,
+    /// A function which can be called with `new`.
+    Fn(Ident),
+    Constructor,
+    /// Methods, getters and setters are never constructed.
+    Method,
+}
+
+impl NewTarget {
+    fn fold_in<T>(&mut self, ctx: Ctx, node: T) -> T
+    where
+        T: FoldWith<Self>,
+    {
+        let old = replace(&mut self.ctx, ctx);
+        let node = node.fold_children(self);
+        self.ctx = old;
+        node
+    }
+}
+
+impl Fold<FnDecl> for NewTarget {
+    fn fold(&mut self, f: FnDecl) -> FnDecl {
+        let ctx = Ctx::Fn(f.ident.clone());
+        self.fold_in(ctx, f)
+    }
+}
+
+impl Fold<FnExpr> for NewTarget {
+    fn fold(&mut self, mut f: FnExpr) -> FnExpr {
+        if f.ident.is_none() && contains_new_target(&f.function) {
+            f.ident = Some(private_ident!("_target"));
+        }
+
+        let ctx = match f.ident {
+            Some(ref ident) => Ctx::Fn(ident.clone()),
+            None => Ctx::Method,
+        };
+        self.fold_in(ctx, f)
+    }
+}
+
+impl Fold<Constructor> for NewTarget {
+    fn fold(&mut self, c: Constructor) -> Constructor {
+        self.fold_in(Ctx::Constructor, c)
+    }
+}
+
+macro_rules! method {
+    ($T:ty) => {
+        impl Fold<$T> for NewTarget {
+            fn fold(&mut self, m: $T) -> $T {
+                self.fold_in(Ctx::Method, m)
+            }
+        }
+    };
+}
+
+method!(ClassMethod);
+method!(PrivateMethod);
+method!(MethodProp);
+method!(GetterProp);
+method!(SetterProp);
+
+impl Fold<Expr> for NewTarget {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+        if let Ctx::Top = self.ctx {
+            return e;
+        }
+
+        match e {
+            Expr::MetaProp(MetaPropExpr { ref meta, ref prop })
+                if meta.sym == *"new" && prop.sym == *"target" =>
+            {
+                let span = e.span();
+                let this = || ThisExpr { span: DUMMY_SP };
+
+                match self.ctx {
+                    Ctx::Top => unreachable!(),
+                    Ctx::Fn(ref ident) => Expr::Cond(CondExpr {
+                        span,
+                        test: box Expr::Bin(BinExpr {
+                            span: DUMMY_SP,
+                            left: box Expr::This(this()),
+                            op: op!("instanceof"),
+                            right: box Expr::Ident(ident.clone()),
+                        }),
+                        cons: box this().member(quote_ident!("constructor")),
+                        alt: undefined(DUMMY_SP),
+                    }),
+                    Ctx::Constructor => this().member(quote_ident!("constructor")),
+                    Ctx::Method => *undefined(span),
+                }
+            }
+            _ => e,
+        }
+    }
+}
+
+/// Returns true if `new.target` of `f` is used, excluding nested functions
+/// which have their own `new.target`.
+fn contains_new_target(f: &Function) -> bool {
+    let mut v = NewTargetFinder { found: false };
+    // `visit_with` would stop at `f` itself.
+    f.visit_children(&mut v);
+    v.found
+}
+
+struct NewTargetFinder {
+    found: bool,
+}
+
+impl Visit<MetaPropExpr> for NewTargetFinder {
+    fn visit(&mut self, e: &MetaPropExpr) {
+        if e.meta.sym == *"new" && e.prop.sym == *"target" {
+            self.found = true;
+        }
+    }
+}
+
+impl Visit<Function> for NewTargetFinder {
+    fn visit(&mut self, _: &Function) {}
+}
+
+impl Visit<Constructor> for NewTargetFinder {
+    fn visit(&mut self, _: &Constructor) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compat::es2015::{arrow, Classes};
+    use swc_common::chain;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| new_target(),
+        fn_decl,
+        "function Foo() { return new.target; }",
+        "function Foo() { return this instanceof Foo ? this.constructor : void 0; }"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| new_target(),
+        anonymous_fn_expr,
+        "var Foo = function () { return new.target; }",
+        "var Foo = function _target() {
+    return this instanceof _target ? this.constructor : void 0;
+}"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| new_target(),
+        fn_expr_in_call,
+        "foo(function () { return new.target; });
+foo(function Bar() { return new.target; });",
+        "foo(function _target() {
+    return this instanceof _target ? this.constructor : void 0;
+});
+foo(function Bar() { return this instanceof Bar ? this.constructor : void 0; });"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| new_target(),
+        class_expr,
+        "var Foo = class {
+    constructor() { this.t = new.target; }
+    method() { return new.target; }
+};",
+        "var Foo = class {
+    constructor() { this.t = this.constructor; }
+    method() { return void 0; }
+};"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| new_target(),
+        class_members,
+        "class Foo {
+    constructor() { this.t = new.target; }
+    method() { return new.target; }
+}",
+        "class Foo {
+    constructor() { this.t = this.constructor; }
+    method() { return void 0; }
+}"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| new_target(),
+        nested_fn,
+        "function Foo() {
+    function Bar() { return new.target; }
+    return new.target;
+}",
+        "function Foo() {
+    function Bar() { return this instanceof Bar ? this.constructor : void 0; }
+    return this instanceof Foo ? this.constructor : void 0;
+}"
+    );
+
+    test_exec!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| chain!(new_target(), arrow()),
+        exec_fn,
+        "function Foo() {
+    const get = () => new.target;
+    return get();
+}
+
+expect(new Foo()).toBe(Foo);
+expect(Foo()).toBe(undefined);
+expect(Foo.call({})).toBe(undefined);"
+    );
+
+    test_exec!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| chain!(new_target(), Classes::default(), arrow()),
+        exec_class,
+        "class Foo {
+    constructor() {
+        this.target = (() => new.target)();
+    }
+}
+class Bar extends Foo {}
+
+expect(new Foo().target).toBe(Foo);
+expect(new Bar().target).toBe(Bar);"
+    );
+
+    test_exec!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| chain!(new_target(), Classes::default(), arrow()),
+        exec_expr,
+        "var Foo = function () {
+    return new.target;
+};
+var Bar = class {
+    constructor() {
+        this.target = new.target;
+    }
+};
+
+expect(new Foo()).toBe(Foo);
+expect(Foo()).toBe(undefined);
+expect(new Bar().target).toBe(Bar);"
+    );
+}
@@ -665,10 +665,16 @@ impl CaseHandler<'_> {
                                 .as_callee(),
                             args: vec![
                                 arg.unwrap().as_arg(),
+                                // `context.delegateYield(iterable, "t0", loc)` stores the
+                                // result in `context.t0`.
                                 match result {
-                                    Expr::Ident(ref i) => i.clone().as_arg(),
+                                    Expr::Member(MemberExpr {
+                                        prop: box Expr::Ident(ref i),
+                                        computed: false,
+                                        ..
+                                    }) => Lit::Str(quote_str!(i.sym.clone())).as_arg(),
                                     _ => unreachable!(
-                                        "make_var() returned something other than ident: {:?}",
+                                        "make_var() returned something other than member: {:?}",
                                         result
                                     ),
                                 },
@@ -955,12 +961,13 @@ impl CaseHandler<'_> {
                 // statements are rare, and all of this logic happens at transform
                 // time, so it has no additional runtime cost.
 
+                let label = s.label.sym.clone();
                 self.with_entry(
                     Entry::Labeled {
-                        label: s.label.sym.clone(),
+                        label: label.clone(),
                         break_loc: after,
                     },
-                    |h| h.explode_stmt(*s.body, None),
+                    |h| h.explode_stmt(*s.body, Some(label)),
                 );
 
                 self.mark(after);
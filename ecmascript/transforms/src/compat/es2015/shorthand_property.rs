@@ -35,6 +35,10 @@ use swc_common::{Fold, FoldWith};
 ///   }
 /// };
 /// ```
+///
+/// Async and generator methods are converted into async / generator function
+/// expressions, so they can be handled by `async_to_generator` and
+/// `regenerator`.
 #[derive(Default, Clone, Copy)]
 pub struct Shorthand;
 
@@ -126,4 +130,132 @@ var o = {
   x: x
 };"
     );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| Shorthand,
+        async_method,
+        "var obj = {
+  async fetch() {
+    return await load();
+  }
+};",
+        "var obj = {
+  fetch: async function () {
+    return await load();
+  }
+};"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| Shorthand,
+        generator_method,
+        "var obj = {
+  *gen() {
+    yield 1;
+  }
+};",
+        "var obj = {
+  gen: function* () {
+    yield 1;
+  }
+};"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| Shorthand,
+        async_generator_method,
+        "var obj = {
+  async *stream() {
+    await ready;
+    yield 1;
+  }
+};",
+        "var obj = {
+  stream: async function* () {
+    await ready;
+    yield 1;
+  }
+};"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| Shorthand,
+        literal_keys,
+        "var obj = {
+  'foo-bar'() {},
+  async 'baz'() {},
+  1() {},
+  *2() {}
+};",
+        "var obj = {
+  'foo-bar': function () {},
+  'baz': async function () {},
+  1: function () {},
+  2: function* () {}
+};"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| Shorthand,
+        computed_key,
+        "var obj = {
+  async *[Symbol.asyncIterator]() {}
+};",
+        "var obj = {
+  [Symbol.asyncIterator]: async function* () {}
+};"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| Shorthand,
+        get_set_shorthand,
+        "var get = 1, set = 2;
+var obj = { get, set, get foo() { return get; } };",
+        "var get = 1, set = 2;
+var obj = {
+  get: get,
+  set: set,
+  get foo() {
+    return get;
+  }
+};"
+    );
+
+    test_exec!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| Shorthand,
+        get_set_shorthand_exec,
+        "var get = 1, set = 2;
+var obj = { get, set, get() { return 3; } };
+expect(obj.get()).toBe(3);
+expect(obj.set).toBe(2);
+expect(Object.getOwnPropertyDescriptor(obj, 'get').value).toBe(obj.get);"
+    );
+
+    test_exec!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| Shorthand,
+        generator_method_exec,
+        "var obj = {
+  *gen() {
+    yield 1;
+    yield 2;
+  },
+  'x'() {
+    return 'x';
+  },
+  3() {
+    return 3;
+  }
+};
+expect(Array.from(obj.gen())).toEqual([1, 2]);
+expect(obj.x()).toBe('x');
+expect(obj[3]()).toBe(3);"
+    );
 }
@@ -69,4 +69,37 @@ mod tests {
         "var re = /o+/;",
         "var re = /o+/;"
     );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| StickyRegex,
+        escape,
+        r#"var re = /\/\"'\n\\/y;"#,
+        r#"var re = new RegExp("\\/\\\"'\\n\\\\", "y");"#,
+        ok_if_code_eq
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| StickyRegex,
+        flags_order,
+        "var re = /o+/ymgi;",
+        "var re = new RegExp('o+', 'ymgi');"
+    );
+
+    test_exec!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| StickyRegex,
+        escape_exec,
+        r#"
+var re = /a\/b\"c'\n\\/y;
+expect(re.source).toBe(/a\/b\"c'\n\\/.source);
+expect(re.sticky).toBe(true);
+expect(re.test("a/b\"c'\n\\")).toBe(true);
+
+var re2 = /[/"]+\d/gy;
+expect(re2.flags).toBe("gy");
+expect(re2.exec('/"1')[0]).toBe('/"1');
+"#
+    );
 }
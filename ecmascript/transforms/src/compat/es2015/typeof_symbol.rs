@@ -20,6 +20,9 @@ use swc_common::{Fold, FoldWith, Visit, VisitWith};
 ///
 /// _typeof(Symbol()) === "symbol";
 /// ```
+///
+/// `typeof` is kept as is if the result is compared with a literal other than
+/// `"symbol"` or `"object"`.
 #[derive(Clone)]
 pub struct TypeOfSymbol;
 
@@ -37,13 +40,38 @@ impl Fold<Expr> for TypeOfSymbol {
                 span,
                 op: op!("typeof"),
                 arg,
-            }) => Expr::Call(CallExpr {
-                span,
-                callee: helper!(span, type_of, "typeof"),
-                args: vec![arg.as_arg()],
+            }) => {
+                let call = Expr::Call(CallExpr {
+                    span,
+                    callee: helper!(span, type_of, "typeof"),
+                    args: vec![arg.clone().as_arg()],
 
-                type_args: Default::default(),
-            }),
+                    type_args: Default::default(),
+                });
+
+                match *arg {
+                    // `typeof x` does not throw even if `x` is not declared, so we
+                    // preserve it.
+                    //
+                    // typeof x === "undefined" ? "undefined" : _typeof(x)
+                    Expr::Ident(..) => Expr::Cond(CondExpr {
+                        span,
+                        test: box Expr::Bin(BinExpr {
+                            span,
+                            left: box Expr::Unary(UnaryExpr {
+                                span,
+                                op: op!("typeof"),
+                                arg,
+                            }),
+                            op: op!("==="),
+                            right: box Expr::Lit(Lit::Str(quote_str!("undefined"))),
+                        }),
+                        cons: box Expr::Lit(Lit::Str(quote_str!("undefined"))),
+                        alt: box call,
+                    }),
+                    _ => call,
+                }
+            }
             _ => expr,
         }
     }
@@ -56,31 +84,41 @@ impl Fold<BinExpr> for TypeOfSymbol {
             _ => return expr.fold_children(self),
         }
 
-        match *expr.left {
-            Expr::Unary(UnaryExpr {
-                op: op!("typeof"), ..
-            }) => {
-                if is_non_symbol_literal(&expr.right) {
-                    return expr;
-                }
-            }
-            _ => {}
+        // Native typeof gives the right answer if the result is compared with a
+        // literal which cannot be the result of typeof for a symbol.
+        if is_non_symbol_literal(&expr.right) {
+            return BinExpr {
+                left: fold_typeof_arg(self, expr.left),
+                ..expr
+            };
         }
-        match *expr.right {
-            Expr::Unary(UnaryExpr {
-                op: op!("typeof"), ..
-            }) => {
-                if is_non_symbol_literal(&expr.left) {
-                    return expr;
-                }
-            }
-            _ => {}
+        if is_non_symbol_literal(&expr.left) {
+            return BinExpr {
+                right: fold_typeof_arg(self, expr.right),
+                ..expr
+            };
         }
 
         expr.fold_children(self)
     }
 }
 
+/// Folds the argument of `typeof` while keeping the operator itself.
+fn fold_typeof_arg(folder: &mut TypeOfSymbol, e: Box<Expr>) -> Box<Expr> {
+    match *e {
+        Expr::Unary(UnaryExpr {
+            span,
+            op: op!("typeof"),
+            arg,
+        }) => box Expr::Unary(UnaryExpr {
+            span,
+            op: op!("typeof"),
+            arg: arg.fold_with(folder),
+        }),
+        _ => e.fold_with(folder),
+    }
+}
+
 fn should_work(node: &Expr) -> bool {
     struct Visitor {
         found: bool,
@@ -97,32 +135,16 @@ fn should_work(node: &Expr) -> bool {
     v.found
 }
 
+/// Returns true for string literals other than `"symbol"` and `"object"`.
+///
+/// A polyfilled symbol is an object, so `typeof sym === "object"` should be
+/// handled by the helper, too.
 fn is_non_symbol_literal(e: &Expr) -> bool {
     match *e {
-        Expr::Lit(Lit::Str(Str {
-            value: js_word!("undefined"),
-            ..
-        }))
-        | Expr::Lit(Lit::Str(Str {
-            value: js_word!("object"),
-            ..
-        }))
-        | Expr::Lit(Lit::Str(Str {
-            value: js_word!("boolean"),
-            ..
-        }))
-        | Expr::Lit(Lit::Str(Str {
-            value: js_word!("number"),
-            ..
-        }))
-        | Expr::Lit(Lit::Str(Str {
-            value: js_word!("string"),
-            ..
-        }))
-        | Expr::Lit(Lit::Str(Str {
-            value: js_word!("function"),
-            ..
-        })) => true,
+        Expr::Lit(Lit::Str(Str { ref value, .. })) => match *value {
+            js_word!("symbol") | js_word!("object") => false,
+            _ => true,
+        },
         _ => false,
     }
 }
@@ -146,4 +168,56 @@ mod tests {
         "'undefined' !== typeof window",
         "'undefined' !== typeof window"
     );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| TypeOfSymbol,
+        non_symbol_literals,
+        "typeof a === 'function';
+typeof a.b == 'bigint';
+'number' != typeof a();",
+        "typeof a === 'function';
+typeof a.b == 'bigint';
+'number' != typeof a();"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| TypeOfSymbol,
+        symbol_or_object_comparison,
+        "typeof a.b === 'symbol';
+typeof a.b === 'object';
+typeof a.b === b;
+var t = typeof a.b;",
+        "_typeof(a.b) === 'symbol';
+_typeof(a.b) === 'object';
+_typeof(a.b) === b;
+var t = _typeof(a.b);"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| TypeOfSymbol,
+        undeclared_ident,
+        "typeof a === 'symbol';",
+        "(typeof a === 'undefined' ? 'undefined' : _typeof(a)) === 'symbol';"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| TypeOfSymbol,
+        nested_in_skipped_comparison,
+        "typeof function () { return typeof a.b; } === 'function';",
+        "typeof function () { return _typeof(a.b); } === 'function';"
+    );
+
+    test_exec!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| TypeOfSymbol,
+        undeclared_ident_exec,
+        "expect(typeof notDeclared).toBe('undefined');
+expect(typeof notDeclared === 'symbol').toBe(false);
+expect(typeof Symbol()).toBe('symbol');
+expect(typeof {}).toBe('object');"
+    );
 }
@@ -1,5 +1,4 @@
 use crate::{
-    compat::es2015::arrow,
     pass::Pass,
     util::{contains_ident_ref, contains_this_expr, ExprFactory, StmtLike},
 };
@@ -80,13 +79,16 @@ impl Fold<MethodProp> for Actual {
         }
         let params = prop.function.params;
 
-        let fn_ref = make_fn_ref(FnExpr {
-            ident: None,
-            function: Function {
-                params: vec![],
-                ..prop.function
+        let fn_ref = make_fn_ref(
+            FnExpr {
+                ident: None,
+                function: Function {
+                    params: vec![],
+                    ..prop.function
+                },
             },
-        });
+            true,
+        );
         let fn_ref = Expr::Call(CallExpr {
             span: DUMMY_SP,
             callee: fn_ref.as_callee(),
@@ -343,10 +345,13 @@ impl Fold<ClassMethod> for Actual {
 
         let mut folder = MethodFolder { vars: vec![] };
         let function = m.function.fold_children(&mut folder);
-        let expr = make_fn_ref(FnExpr {
-            ident: None,
-            function,
-        });
+        let expr = make_fn_ref(
+            FnExpr {
+                ident: None,
+                function,
+            },
+            true,
+        );
 
         let hoisted_super = if folder.vars.is_empty() {
             None
@@ -440,7 +445,7 @@ impl Fold<Expr> for Actual {
                     .fold_children(self);
                 }
 
-                return make_fn_ref(fn_expr);
+                return make_fn_ref(fn_expr, true);
             }
 
             _ => {}
@@ -449,16 +454,44 @@ impl Fold<Expr> for Actual {
         let expr = expr.fold_children(self);
 
         match expr {
-            Expr::Arrow(ArrowExpr { is_async: true, .. }) => {
-                // Apply arrow
-                let expr = expr.fold_with(&mut arrow());
-
-                let f = match expr {
-                    Expr::Fn(f) => f,
-                    _ => return expr,
+            Expr::Arrow(ArrowExpr {
+                span,
+                params,
+                body,
+                is_async: true,
+                is_generator,
+                type_params,
+                return_type,
+            }) => {
+                // The generator is bound to the `this` of the arrow's scope, so
+                // the arrow is lowered here instead of by the `arrow` pass.
+                let body = match body {
+                    BlockStmtOrExpr::BlockStmt(block) => block,
+                    BlockStmtOrExpr::Expr(expr) => BlockStmt {
+                        span: DUMMY_SP,
+                        stmts: vec![Stmt::Return(ReturnStmt {
+                            span: expr.span(),
+                            arg: Some(expr),
+                        })],
+                    },
                 };
 
-                return make_fn_ref(f);
+                return make_fn_ref(
+                    FnExpr {
+                        ident: None,
+                        function: Function {
+                            span,
+                            params,
+                            decorators: Default::default(),
+                            body: Some(body),
+                            is_async: true,
+                            is_generator,
+                            type_params,
+                            return_type,
+                        },
+                    },
+                    true,
+                );
             }
 
             Expr::Fn(
@@ -548,10 +581,14 @@ impl Actual {
         let ident = raw_ident.clone().unwrap_or_else(|| quote_ident!("ref"));
 
         let real_fn_ident = private_ident!(ident.span, format!("_{}", ident.sym));
-        let right = make_fn_ref(FnExpr {
-            ident: None,
-            function: f,
-        });
+        // `this` is passed by `_foo.apply(this, arguments)`.
+        let right = make_fn_ref(
+            FnExpr {
+                ident: None,
+                function: f,
+            },
+            false,
+        );
 
         if is_decl {
             let real_fn = FnDecl {
@@ -670,7 +707,10 @@ impl Actual {
 /// Creates
 ///
 /// `_asyncToGenerator(function*() {})` from `async function() {}`;
-fn make_fn_ref(mut expr: FnExpr) -> Expr {
+///
+/// If `bind_this` is true and the function uses `this`, the generator is
+/// bound to the `this` of the current scope.
+fn make_fn_ref(mut expr: FnExpr, bind_this: bool) -> Expr {
     struct AwaitToYield;
 
     macro_rules! noop {
@@ -711,8 +751,7 @@ fn make_fn_ref(mut expr: FnExpr) -> Expr {
 
     let span = expr.span();
 
-    let contains_this = contains_this_expr(&expr.function.body);
-    let expr = if contains_this {
+    let expr = if bind_this && contains_this_expr(&expr.function.body) {
         Expr::Call(CallExpr {
             span: DUMMY_SP,
             callee: validate!(expr.member(quote_ident!("bind"))).as_callee(),
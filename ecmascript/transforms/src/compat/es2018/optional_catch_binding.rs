@@ -1,6 +1,8 @@
 use crate::pass::Pass;
 use ast::*;
-use swc_common::Fold;
+use fxhash::FxHashSet;
+use swc_atoms::JsWord;
+use swc_common::{Fold, FoldWith, Visit, VisitWith};
 
 struct OptionalCatchBinding;
 
@@ -10,17 +12,47 @@ pub fn optional_catch_binding() -> impl Pass {
 
 impl Fold<CatchClause> for OptionalCatchBinding {
     fn fold(&mut self, cc: CatchClause) -> CatchClause {
+        let cc = cc.fold_children(self);
+
         if cc.param.is_some() {
             return cc;
         }
 
+        // The binding is marked, so hygiene can rename it, but we also avoid
+        // names used in the body (including bindings of nested catch clauses)
+        // so that the output is correct even without hygiene.
+        let mut v = UsedNames::default();
+        cc.body.visit_with(&mut v);
+
+        let name = (0..)
+            .map(|i| -> JsWord {
+                match i {
+                    0 => "e".into(),
+                    1 => "_e".into(),
+                    _ => format!("_e{}", i - 1).into(),
+                }
+            })
+            .find(|name| !v.names.contains(name))
+            .unwrap();
+
         CatchClause {
-            param: Some(private_ident!("e").into()),
+            param: Some(private_ident!(name).into()),
             ..cc
         }
     }
 }
 
+#[derive(Default)]
+struct UsedNames {
+    names: FxHashSet<JsWord>,
+}
+
+impl Visit<Ident> for UsedNames {
+    fn visit(&mut self, i: &Ident) {
+        self.names.insert(i.sym.clone());
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::optional_catch_binding as tr;
@@ -32,4 +64,56 @@ mod tests {
         "try {} catch {}",
         "try {} catch(e) {}"
     );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(),
+        body_uses_name,
+        "var e = 1, _unused = 2;
+try {} catch {
+    console.log(e, _unused);
+}",
+        "var e = 1, _unused = 2;
+try {} catch(_e) {
+    console.log(e, _unused);
+}"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(),
+        body_uses_fallback_names,
+        "try {} catch {
+    e(_e, _e1);
+}",
+        "try {} catch(_e2) {
+    e(_e, _e1);
+}"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(),
+        nested,
+        "try {} catch {
+    try {} catch {}
+}",
+        "try {} catch(_e) {
+    try {} catch(e) {}
+}"
+    );
+
+    test_exec!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| tr(),
+        no_shadowing,
+        "var e = 'outer';
+var result;
+try {
+    throw new Error('inner');
+} catch {
+    result = e;
+}
+expect(result).toBe('outer');"
+    );
 }
@@ -3,6 +3,7 @@ pub use self::{
 };
 use crate::pass::Pass;
 use ast::Expr;
+use swc_common::chain;
 
 mod member_expr_lits;
 mod prop_lits;
@@ -10,10 +11,34 @@ mod reserved_word;
 
 /// Make output es3-compatible.
 pub fn es3(preserve_import: bool) -> impl Pass {
-    chain_at!(
-        Expr,
-        PropertyLiteral,
-        MemberExprLit,
+    chain!(
+        chain_at!(Expr, PropertyLiteral::default(), MemberExprLit),
+        // Renaming requires the whole program.
         ReservedWord { preserve_import }
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| es3(false),
+        reserved_word,
+        "var char = { int: 1 };
+var x = char.int;",
+        "var _char = { 'int': 1 };
+var x = _char['int'];"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| es3(false),
+        member_expr_callees,
+        "new foo.class(a.b.catch(fn));
+a.catch.bind(a)`tpl`;",
+        "new foo['class'](a.b['catch'](fn));
+a['catch'].bind(a)`tpl`;"
+    );
+}
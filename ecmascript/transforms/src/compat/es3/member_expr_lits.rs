@@ -72,6 +72,7 @@ impl Fold<MemberExpr> for MemberExprLit {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use swc_common::DUMMY_SP;
 
     test!(
         ::swc_ecma_parser::Syntax::default(),
@@ -102,4 +103,58 @@ obj["var"] = "isKeyword";"#
         "_query[idx]=$this.attr('data-ref');",
         "_query[idx]=$this.attr('data-ref');"
     );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| MemberExprLit,
+        chained,
+        "a.b.catch(fn);
+a.catch.bind(a);
+a.default.class.b;
+this.finally().new;",
+        "a.b['catch'](fn);
+a['catch'].bind(a);
+a['default']['class'].b;
+this['finally']()['new'];"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| MemberExprLit,
+        callees,
+        "new foo.class();
+new (a.default.b)();
+a.delete`tpl`;
+foo(a.in)();",
+        "new foo['class']();
+new a['default'].b();
+a['delete']`tpl`;
+foo(a['in'])();"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| MemberExprLit,
+        nested_in_computed,
+        "a[b.var].c[d.if]",
+        "a[b['var']].c[d['if']]"
+    );
+
+    #[test]
+    fn numeric_ident() {
+        // `obj.0` cannot be parsed, so the ast is built by hand.
+        let e = MemberExpr {
+            span: DUMMY_SP,
+            obj: ExprOrSuper::Expr(box Expr::Ident(quote_ident!("obj"))),
+            prop: box Expr::Ident(quote_ident!("0")),
+            computed: false,
+        }
+        .fold_with(&mut MemberExprLit);
+
+        assert!(e.computed);
+        match *e.prop {
+            Expr::Lit(Lit::Str(ref s)) => assert_eq!(&*s.value, "0"),
+            _ => panic!("expected a string literal, got {:?}", e.prop),
+        }
+    }
 }
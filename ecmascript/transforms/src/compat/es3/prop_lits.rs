@@ -1,5 +1,6 @@
 use crate::util::is_valid_ident;
 use ast::*;
+use swc_atoms::JsWord;
 use swc_common::{Fold, FoldWith};
 
 /// babel: `transform-property-literals`
@@ -30,7 +31,21 @@ use swc_common::{Fold, FoldWith};
 /// };
 /// ```
 #[derive(Default, Clone, Copy)]
-pub struct PropertyLiteral;
+pub struct PropertyLiteral {
+    /// Also quote words reserved in strict mode or modules of es5 and later
+    /// (e.g. `let`, `yield`) and numeric keys.
+    ///
+    /// Numeric keys are printed as strings, so engines which mishandle
+    /// numeric keys (e.g. `007`) see the canonical form.
+    pub es5_compat: bool,
+}
+
+impl PropertyLiteral {
+    fn should_quote(self, sym: &JsWord) -> bool {
+        sym.is_reserved_for_es3()
+            || (self.es5_compat && ["let", "yield", "await"].contains(&&**sym))
+    }
+}
 
 impl Fold<Module> for PropertyLiteral {
     fn fold(&mut self, node: Module) -> Module {
@@ -46,7 +61,7 @@ impl Fold<PropName> for PropertyLiteral {
             PropName::Str(Str {
                 value: sym, span, ..
             }) => {
-                if sym.is_reserved_for_es3() || !is_valid_ident(&sym) {
+                if self.should_quote(&sym) || !is_valid_ident(&sym) {
                     PropName::Str(Str {
                         span,
                         value: sym,
@@ -58,7 +73,7 @@ impl Fold<PropName> for PropertyLiteral {
             }
             PropName::Ident(i) => {
                 let Ident { sym, span, .. } = i;
-                if sym.is_reserved_for_es3() || sym.contains('-') || sym.contains('.') {
+                if self.should_quote(&sym) || sym.contains('-') || sym.contains('.') {
                     PropName::Str(Str {
                         span,
                         value: sym,
@@ -68,6 +83,19 @@ impl Fold<PropName> for PropertyLiteral {
                     PropName::Ident(Ident { span, sym, ..i })
                 }
             }
+            // Only integers are printed the same way by rust and js.
+            PropName::Num(Number { span, value })
+                if self.es5_compat
+                    && value >= 0.0
+                    && value.fract() == 0.0
+                    && value <= 9_007_199_254_740_991.0 =>
+            {
+                PropName::Str(Str {
+                    span,
+                    value: format!("{}", value).into(),
+                    has_escape: false,
+                })
+            }
             _ => n,
         }
     }
@@ -79,7 +107,7 @@ mod tests {
 
     test!(
         ::swc_ecma_parser::Syntax::default(),
-        |_| PropertyLiteral,
+        |_| PropertyLiteral::default(),
         babel_basic,
         r#"var foo = {
   // changed
@@ -104,7 +132,7 @@ mod tests {
 
     test!(
         ::swc_ecma_parser::Syntax::default(),
-        |_| PropertyLiteral,
+        |_| PropertyLiteral::default(),
         str_lit,
         r#"'use strict';
 var x = {
@@ -116,4 +144,65 @@ var x = {
 };"#,
         ok_if_code_eq
     );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| PropertyLiteral::default(),
+        unquote_modern_words,
+        r#"var x = {
+  "let": 1,
+  yield: 2,
+  "class": 3,
+  1: 4,
+  "foo": 5
+};"#,
+        r#"var x = {
+  let: 1,
+  yield: 2,
+  "class": 3,
+  1: 4,
+  foo: 5
+};"#,
+        ok_if_code_eq
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| PropertyLiteral { es5_compat: true },
+        es5_compat,
+        r#"var x = {
+  let: 1,
+  "yield": 2,
+  await: 3,
+  enum: 4,
+  extends: 5,
+  "foo": 6,
+  007: 7,
+  1.5: 8,
+  "010": 9
+};"#,
+        r#"var x = {
+  "let": 1,
+  "yield": 2,
+  "await": 3,
+  "enum": 4,
+  "extends": 5,
+  foo: 6,
+  "7": 7,
+  1.5: 8,
+  "010": 9
+};"#,
+        ok_if_code_eq
+    );
+
+    test_exec!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| PropertyLiteral { es5_compat: true },
+        es5_compat_exec,
+        r#"var x = { let: 1, yield: 2, 007: 3, 10: 4 };
+expect(x.let).toBe(1);
+expect(x['yield']).toBe(2);
+expect(x[7]).toBe(3);
+expect(Object.keys(x)).toEqual(['7', '10', 'let', 'yield']);"#
+    );
 }
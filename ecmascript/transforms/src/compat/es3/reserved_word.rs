@@ -1,5 +1,8 @@
+use crate::util::{find_ids, ident::IdentLike, DestructuringFinder, Id};
 use ast::*;
-use swc_common::{Fold, FoldWith};
+use hashbrown::{HashMap, HashSet};
+use swc_atoms::JsWord;
+use swc_common::{Fold, FoldWith, Visit, VisitWith, DUMMY_SP};
 
 /// babel: `@babel/plugin-transform-reserved-words`
 ///
@@ -7,6 +10,9 @@ use swc_common::{Fold, FoldWith};
 /// reserved in ES5 and later. This plugin, to be used when targeting ES3
 /// environments, renames variables from that set of words.
 ///
+/// Bindings are renamed along with references to them, so this should run
+/// after the resolver. Exported bindings keep their exported names.
+///
 /// # Input
 /// ```js
 /// var abstract = 1;
@@ -23,47 +29,134 @@ pub struct ReservedWord {
     pub preserve_import: bool,
 }
 
-impl Fold<Ident> for ReservedWord {
-    fn fold(&mut self, i: Ident) -> Ident {
-        fold_ident(self.preserve_import, i)
+impl Fold<Module> for ReservedWord {
+    fn fold(&mut self, node: Module) -> Module {
+        let mut r = Renamer::new(self.preserve_import, &node);
+        validate!(node.fold_with(&mut r))
+    }
+}
+
+impl Fold<Script> for ReservedWord {
+    fn fold(&mut self, node: Script) -> Script {
+        let mut r = Renamer::new(self.preserve_import, &node);
+        node.fold_with(&mut r)
     }
 }
 
-macro_rules! noop {
-    ($T:tt) => {
-        impl Fold<$T> for ReservedWord {
-            fn fold(&mut self, node: $T) -> $T {
-                node
+/// Collects bindings named with reserved words and all used symbols.
+struct BindingCollector {
+    preserve_import: bool,
+    bindings: HashSet<Id>,
+    used: HashSet<JsWord>,
+}
+
+impl BindingCollector {
+    fn add<T>(&mut self, node: &T)
+    where
+        T: for<'any> VisitWith<DestructuringFinder<'any, Ident>>,
+    {
+        for i in find_ids::<_, Ident>(node) {
+            if self.preserve_import && i.sym == *"import" {
+                continue;
+            }
+
+            if i.is_reserved_for_es3() {
+                self.bindings.insert(i.to_id());
             }
         }
-    };
+    }
 }
-noop!(PropName);
-noop!(ExportSpecifier);
 
-impl Fold<ImportSpecific> for ReservedWord {
-    fn fold(&mut self, s: ImportSpecific) -> ImportSpecific {
-        if s.imported.is_some() {
-            ImportSpecific {
-                local: s.local.fold_with(self),
-                ..s
+impl Visit<Ident> for BindingCollector {
+    fn visit(&mut self, i: &Ident) {
+        self.used.insert(i.sym.clone());
+    }
+}
+
+macro_rules! collect {
+    ($T:tt, |$n:ident| $e:expr) => {
+        impl Visit<$T> for BindingCollector {
+            fn visit(&mut self, $n: &$T) {
+                self.add(&$e);
+                $n.visit_children(self);
             }
-        } else {
-            ImportSpecific {
-                imported: s.imported.fold_with(self),
-                ..s
+        }
+    };
+}
+collect!(VarDeclarator, |n| n.name);
+collect!(Function, |n| n.params);
+collect!(ArrowExpr, |n| n.params);
+collect!(CatchClause, |n| n.param);
+collect!(SetterProp, |n| n.param);
+collect!(FnDecl, |n| n.ident);
+collect!(ClassDecl, |n| n.ident);
+collect!(FnExpr, |n| n.ident);
+collect!(ClassExpr, |n| n.ident);
+collect!(ImportSpecific, |n| n.local);
+collect!(ImportDefault, |n| n.local);
+collect!(ImportStarAs, |n| n.local);
+
+/// Renames bindings collected by [BindingCollector] and references to them.
+struct Renamer {
+    names: HashMap<Id, JsWord>,
+}
+
+impl Renamer {
+    fn new<T>(preserve_import: bool, node: &T) -> Self
+    where
+        T: VisitWith<BindingCollector>,
+    {
+        let mut v = BindingCollector {
+            preserve_import,
+            bindings: Default::default(),
+            used: Default::default(),
+        };
+        node.visit_with(&mut v);
+
+        let mut bindings = v.bindings.into_iter().collect::<Vec<_>>();
+        // Make generated names independent of the iteration order.
+        bindings.sort_by(|a, b| (&*a.0, a.1).cmp(&(&*b.0, b.1)));
+
+        let mut used = v.used;
+        let mut names = HashMap::default();
+        for id in bindings {
+            let mut name: JsWord = format!("_{}", id.0).into();
+            let mut n = 2;
+            while used.contains(&name) {
+                name = format!("_{}{}", id.0, n).into();
+                n += 1;
             }
+            used.insert(name.clone());
+            names.insert(id, name);
         }
+
+        Renamer { names }
+    }
+
+    fn renamed(&self, i: &Ident) -> Option<Ident> {
+        self.names.get(&i.to_id()).map(|sym| Ident {
+            sym: sym.clone(),
+            ..i.clone()
+        })
     }
 }
 
-impl Fold<Module> for ReservedWord {
-    fn fold(&mut self, node: Module) -> Module {
-        validate!(node.fold_children(self))
+impl Fold<Ident> for Renamer {
+    fn fold(&mut self, i: Ident) -> Ident {
+        self.renamed(&i).unwrap_or(i)
     }
 }
 
-impl Fold<MemberExpr> for ReservedWord {
+impl Fold<PropName> for Renamer {
+    fn fold(&mut self, n: PropName) -> PropName {
+        match n {
+            PropName::Computed(..) => n.fold_children(self),
+            _ => n,
+        }
+    }
+}
+
+impl Fold<MemberExpr> for Renamer {
     fn fold(&mut self, e: MemberExpr) -> MemberExpr {
         if e.computed {
             MemberExpr {
@@ -80,24 +173,154 @@ impl Fold<MemberExpr> for ReservedWord {
     }
 }
 
-fn fold_ident(preserve_import: bool, i: Ident) -> Ident {
-    if preserve_import && i.sym == *"import" {
-        return i;
+impl Fold<Prop> for Renamer {
+    fn fold(&mut self, p: Prop) -> Prop {
+        match p {
+            // `{ char }` => `{ char: _char }`
+            Prop::Shorthand(i) => match self.renamed(&i) {
+                Some(value) => Prop::KeyValue(KeyValueProp {
+                    key: PropName::Ident(i),
+                    value: box Expr::Ident(value),
+                }),
+                None => Prop::Shorthand(i),
+            },
+            _ => p.fold_children(self),
+        }
     }
+}
 
-    if i.is_reserved_for_es3() {
-        return Ident {
-            sym: format!("_{}", i.sym).into(),
-            ..i
-        };
+impl Fold<ObjectPatProp> for Renamer {
+    fn fold(&mut self, p: ObjectPatProp) -> ObjectPatProp {
+        match p {
+            // `{ char = 1 }` => `{ char: _char = 1 }`
+            ObjectPatProp::Assign(AssignPatProp { span, key, value }) => {
+                let value = value.fold_with(self);
+
+                match self.renamed(&key) {
+                    Some(renamed) => ObjectPatProp::KeyValue(KeyValuePatProp {
+                        key: PropName::Ident(key),
+                        value: box match value {
+                            Some(right) => Pat::Assign(AssignPat {
+                                span,
+                                left: box Pat::Ident(renamed),
+                                right,
+                                type_ann: None,
+                            }),
+                            None => Pat::Ident(renamed),
+                        },
+                    }),
+                    None => ObjectPatProp::Assign(AssignPatProp { span, key, value }),
+                }
+            }
+            _ => p.fold_children(self),
+        }
     }
+}
 
-    i
+impl Fold<ImportSpecific> for Renamer {
+    fn fold(&mut self, s: ImportSpecific) -> ImportSpecific {
+        match self.renamed(&s.local) {
+            Some(local) => ImportSpecific {
+                imported: Some(s.imported.unwrap_or(s.local)),
+                local,
+                ..s
+            },
+            None => s,
+        }
+    }
+}
+
+impl Fold<NamedExport> for Renamer {
+    fn fold(&mut self, e: NamedExport) -> NamedExport {
+        // Names of re-exports are not bindings of this module.
+        if e.src.is_some() {
+            return e;
+        }
+
+        e.fold_children(self)
+    }
+}
+
+impl Fold<ExportSpecifier> for Renamer {
+    fn fold(&mut self, s: ExportSpecifier) -> ExportSpecifier {
+        match s {
+            // `export { char }` => `export { _char as char }`
+            ExportSpecifier::Named(NamedExportSpecifier {
+                span,
+                orig,
+                exported,
+            }) => match self.renamed(&orig) {
+                Some(renamed) => ExportSpecifier::Named(NamedExportSpecifier {
+                    span,
+                    exported: Some(exported.unwrap_or(orig)),
+                    orig: renamed,
+                }),
+                None => ExportSpecifier::Named(NamedExportSpecifier {
+                    span,
+                    orig,
+                    exported,
+                }),
+            },
+            _ => s,
+        }
+    }
+}
+
+impl Fold<Vec<ModuleItem>> for Renamer {
+    fn fold(&mut self, items: Vec<ModuleItem>) -> Vec<ModuleItem> {
+        let mut buf = Vec::with_capacity(items.len());
+
+        for item in items {
+            match item {
+                // `export var char = 1` => `var _char = 1; export { _char as char }`
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { span, decl })) => {
+                    let ids: Vec<Ident> = match decl {
+                        Decl::Class(ClassDecl { ref ident, .. })
+                        | Decl::Fn(FnDecl { ref ident, .. }) => vec![ident.clone()],
+                        Decl::Var(ref v) => find_ids(&v.decls),
+                        _ => vec![],
+                    };
+
+                    if ids.iter().all(|i| self.renamed(i).is_none()) {
+                        buf.push(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                            span,
+                            decl: decl.fold_with(self),
+                        })));
+                        continue;
+                    }
+
+                    buf.push(ModuleItem::Stmt(Stmt::Decl(decl.fold_with(self))));
+                    buf.push(ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(
+                        NamedExport {
+                            span,
+                            specifiers: ids
+                                .into_iter()
+                                .map(|orig| {
+                                    ExportSpecifier::Named(NamedExportSpecifier {
+                                        span: DUMMY_SP,
+                                        orig,
+                                        exported: None,
+                                    })
+                                })
+                                .collect(),
+                            src: None,
+                        }
+                        .fold_with(self),
+                    )));
+                }
+                _ => buf.push(item.fold_with(self)),
+            }
+        }
+
+        buf
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::resolver::resolver;
+    use swc_common::chain;
 
     macro_rules! identical {
         ($name:ident, $src:literal) => {
@@ -140,4 +363,112 @@ function utf8CheckByte(_byte) {
     );
 
     identical!(export_as_default, "export { Foo as default }");
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| chain!(
+            resolver(),
+            ReservedWord {
+                preserve_import: false
+            }
+        ),
+        param_in_closure,
+        "function f(native) {
+  return function () {
+    return { native, value: native.value };
+  };
+}",
+        "function f(_native) {
+  return function () {
+    return { native: _native, value: _native.value };
+  };
+}"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| chain!(
+            resolver(),
+            ReservedWord {
+                preserve_import: false
+            }
+        ),
+        collision,
+        "var _char = 0;
+var char = 1;
+function boolean() {
+  return char + _char;
+}",
+        "var _char = 0;
+var _char2 = 1;
+function _boolean() {
+  return _char2 + _char;
+}"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| chain!(
+            resolver(),
+            ReservedWord {
+                preserve_import: false
+            }
+        ),
+        global_reference,
+        "foo.char = char;",
+        "foo.char = char;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| chain!(
+            resolver(),
+            ReservedWord {
+                preserve_import: false
+            }
+        ),
+        object_pattern,
+        "var { char, byte = 1, int: x } = obj;",
+        "var { char: _char, byte: _byte = 1, int: x } = obj;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| chain!(
+            resolver(),
+            ReservedWord {
+                preserve_import: false
+            }
+        ),
+        export_decl,
+        "export var char = 1, other = 2;
+export function boolean() {
+  return char;
+}",
+        "var _char = 1, other = 2;
+export { _char as char, other };
+function _boolean() {
+  return _char;
+}
+export { _boolean as boolean };"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| chain!(
+            resolver(),
+            ReservedWord {
+                preserve_import: false
+            }
+        ),
+        export_named,
+        "import { int, float as f } from 'a';
+var char = int + f;
+export { char, char as c };
+export { byte } from 'b';",
+        "import { int as _int, float as f } from 'a';
+var _char = _int + f;
+export { _char as char, _char as c };
+export { byte } from 'b';"
+    );
 }
@@ -45,7 +45,7 @@ macro_rules! add_to {
             };
         }
 
-        let enable = $b.load(Ordering::Relaxed);
+        let enable = $b.swap(false, Ordering::Relaxed);
         if enable {
             $buf.extend(
                 STMTS
@@ -116,12 +116,14 @@ macro_rules! define_helpers {
         }
 
         impl InjectHelpers {
-            fn is_helper_used(&self) -> bool{
+            /// Returns true if any helper is used, and marks all helpers as
+            /// unused.
+            fn take_used(&self) -> bool{
                 let mut value = false;
 
                 HELPERS.with(|helpers|{
                     $(
-                        value |= helpers.inner.$name.load(Ordering::Relaxed);
+                        value |= helpers.inner.$name.swap(false, Ordering::Relaxed);
                     )*
                 });
 
@@ -169,6 +171,7 @@ define_helpers!(Helpers {
     create_class: (),
     decorate: (to_array, to_property_key),
     defaults: (),
+    define_accessor: (),
     define_enumerable_properties: (),
     define_property: (),
     extends: (),
@@ -193,6 +196,7 @@ define_helpers!(Helpers {
     object_without_properties: (object_without_properties_loose),
     object_without_properties_loose: (),
     possible_constructor_return: (type_of, assert_this_initialized),
+    react_element_type: (),
     read_only_error: (),
     set: (super_prop_base, define_property),
     set_prototype_of: (),
@@ -206,8 +210,9 @@ define_helpers!(Helpers {
     super_prop_base: (get_prototype_of),
     tagged_template_literal: (),
     tagged_template_literal_loose: (),
-    // temporal_ref: (temporal_undefined),
-    // temporal_undefined: (),
+    tdz_error: (),
+    temporal_ref: (temporal_undefined, tdz_error),
+    temporal_undefined: (),
     throw: (),
     to_array: (array_with_holes, iterable_to_array, non_iterable_rest),
     to_consumable_array: (array_without_holes, iterable_to_array, non_iterable_spread),
@@ -225,13 +230,18 @@ define_helpers!(Helpers {
     class_private_field_destructure: (),
 });
 
+/// Injects helpers enabled by previous passes.
+///
+/// Each helper is injected only once, so running this pass again is a noop
+/// unless new helpers are enabled in between. If helpers are external, a
+/// namespace import of `@swc/helpers` is injected instead.
 #[derive(Clone)]
 pub struct InjectHelpers;
 impl InjectHelpers {
     fn mk_helpers(&self) -> Vec<ModuleItem> {
         let (mark, external) = HELPERS.with(|helper| (helper.mark(), helper.external()));
         if external {
-            if self.is_helper_used() {
+            if self.take_used() {
                 vec![ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
                     span: DUMMY_SP,
                     specifiers: vec![ImportSpecifier::Namespace(ImportStarAs {
@@ -251,6 +261,11 @@ impl InjectHelpers {
 
 impl Fold<Module> for InjectHelpers {
     fn fold(&mut self, module: Module) -> Module {
+        // Helpers cannot be enabled without `HELPERS`.
+        if !HELPERS.is_set() {
+            return module;
+        }
+
         let mut module = validate!(module);
         let helpers = self.mk_helpers();
 
@@ -269,6 +284,7 @@ impl Fold<Span> for Marker {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use swc_common::chain;
 
     #[test]
     fn external_helper() {
@@ -364,4 +380,74 @@ let x = 4;",
             false,
         );
     }
+
+    fn count_helpers(module: &Module) -> (usize, usize, usize) {
+        let count = |name: &str| {
+            module
+                .body
+                .iter()
+                .filter(|item| match item {
+                    ModuleItem::Stmt(Stmt::Decl(Decl::Fn(f))) => f.ident.sym == *name,
+                    _ => false,
+                })
+                .count()
+        };
+        let imports = module
+            .body
+            .iter()
+            .filter(|item| match item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => {
+                    import.src.value == *"@swc/helpers"
+                }
+                _ => false,
+            })
+            .count();
+
+        (
+            count("_classCallCheck"),
+            count("_toConsumableArray"),
+            imports,
+        )
+    }
+
+    const CLASS_AND_SPREAD: &str = "class Foo {}
+class Bar {}
+foo(...a);
+bar(...b);";
+
+    #[test]
+    fn inject_once() {
+        crate::tests::Tester::run(|tester| {
+            let tr = chain!(
+                crate::compat::es2015::Classes::default(),
+                crate::compat::es2015::spread(Default::default()),
+                InjectHelpers,
+                InjectHelpers
+            );
+            let module =
+                tester.apply_transform(tr, "input.js", Default::default(), CLASS_AND_SPREAD)?;
+
+            assert_eq!(count_helpers(&module), (1, 1, 0));
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn inject_once_external() {
+        crate::tests::Tester::run(|tester| {
+            HELPERS.set(&Helpers::new(true), || {
+                let tr = chain!(
+                    crate::compat::es2015::Classes::default(),
+                    crate::compat::es2015::spread(Default::default()),
+                    InjectHelpers,
+                    InjectHelpers
+                );
+                let module =
+                    tester.apply_transform(tr, "input.js", Default::default(), CLASS_AND_SPREAD)?;
+
+                assert_eq!(count_helpers(&module), (0, 0, 1));
+                Ok(())
+            })
+        })
+    }
 }
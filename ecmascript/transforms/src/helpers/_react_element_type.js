@@ -0,0 +1,9 @@
+var REACT_ELEMENT_TYPE;
+
+function _reactElementType() {
+  if (!REACT_ELEMENT_TYPE) {
+    REACT_ELEMENT_TYPE = typeof Symbol === "function" && Symbol.for && Symbol.for("react.element") || 0xeac7;
+  }
+
+  return REACT_ELEMENT_TYPE;
+}
@@ -0,0 +1,3 @@
+function _tdzError(name) {
+  throw new ReferenceError(name + " is not defined - temporal dead zone");
+}
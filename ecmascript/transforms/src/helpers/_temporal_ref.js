@@ -0,0 +1,3 @@
+function _temporalRef(val, name) {
+  return val === _temporalUndefined ? _tdzError(name) : val;
+}
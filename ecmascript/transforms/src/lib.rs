@@ -35,6 +35,7 @@ pub mod proposals;
 pub mod react;
 mod resolver;
 pub mod scope;
+pub mod testing;
 pub mod typescript;
 pub mod util {
     pub use utils::*;
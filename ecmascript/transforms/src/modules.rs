@@ -1,6 +1,9 @@
+pub use self::cjs_to_esm::cjs_to_esm;
+
 #[macro_use]
 pub mod util;
 pub mod amd;
+mod cjs_to_esm;
 pub mod common_js;
 pub mod import_analysis;
 pub mod umd;
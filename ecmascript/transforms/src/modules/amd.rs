@@ -4,7 +4,7 @@ use super::util::{
 };
 use crate::{
     pass::Pass,
-    util::{prepend_stmts, var::VarCollector, DestructuringFinder, ExprFactory},
+    util::{prepend_stmts, DestructuringFinder, ExprFactory},
 };
 use ast::*;
 use fxhash::FxHashSet;
@@ -54,6 +54,9 @@ impl Fold<Module> for Amd {
         let mut exports = vec![];
         let mut initialized = FxHashSet::default();
         let mut export_alls = vec![];
+
+        self.scope.collect_exported_vars(&items);
+
         let mut emitted_esmodule = false;
         let mut has_export = false;
         let exports_ident = self.exports.0.clone();
@@ -164,21 +167,12 @@ impl Fold<Module> for Amd {
                         }) => {
                             extra_stmts.push(Stmt::Decl(Decl::Var(var.clone().fold_with(self))));
 
-                            var.decls.visit_with(&mut VarCollector {
-                                to: &mut self.scope.declared_vars,
-                            });
-
                             let mut found: Vec<Ident> = vec![];
                             for decl in var.decls {
                                 let mut v = DestructuringFinder { found: &mut found };
                                 decl.visit_with(&mut v);
 
                                 for ident in found.drain(..) {
-                                    self.scope
-                                        .exported_vars
-                                        .entry((ident.sym.clone(), ident.span.ctxt()))
-                                        .or_default()
-                                        .push((ident.sym.clone(), ident.span.ctxt()));
                                     init_export!(ident.sym);
 
                                     extra_stmts.push(
@@ -306,22 +300,6 @@ impl Fold<Module> for Amd {
                             {
                                 let is_import_default = orig.sym == js_word!("default");
 
-                                let key = (orig.sym.clone(), orig.span.ctxt());
-                                if self.scope.declared_vars.contains(&key) {
-                                    self.scope
-                                        .exported_vars
-                                        .entry(key.clone())
-                                        .or_default()
-                                        .push(
-                                            exported
-                                                .clone()
-                                                .map(|i| (i.sym.clone(), i.span.ctxt()))
-                                                .unwrap_or_else(|| {
-                                                    (orig.sym.clone(), orig.span.ctxt())
-                                                }),
-                                        );
-                                }
-
                                 if let Some(ref src) = export.src {
                                     if is_import_default {
                                         self.scope
@@ -579,23 +557,6 @@ impl Fold<Expr> for Amd {
     }
 }
 
-impl Fold<VarDecl> for Amd {
-    ///
-    /// - collects all declared variables for let and var.
-    fn fold(&mut self, var: VarDecl) -> VarDecl {
-        if var.kind != VarDeclKind::Const {
-            var.decls.visit_with(&mut VarCollector {
-                to: &mut self.scope.declared_vars,
-            });
-        }
-
-        VarDecl {
-            decls: var.decls.fold_with(self),
-            ..var
-        }
-    }
-}
-
 impl ModulePass for Amd {
     fn config(&self) -> &util::Config {
         &self.config.config
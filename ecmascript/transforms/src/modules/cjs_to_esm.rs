@@ -0,0 +1,626 @@
+use crate::{
+    pass::Pass,
+    util::{is_valid_ident, HANDLER},
+};
+use ast::*;
+use swc_atoms::{js_word, JsWord};
+use swc_common::{Fold, Span, Spanned, Visit, VisitWith, DUMMY_SP};
+
+/// Converts CommonJS `require` calls and exports into es modules.
+///
+/// This is a best-effort conversion for analysis pipelines. Only statically
+/// analyzable patterns are converted, and everything else is left as is with
+/// a warning.
+///
+///  - `const foo = require('foo')` and `require('foo')` are converted if they
+///    are placed before any other statement, because imports are hoisted.
+///  - `const { a, b: c } = require('foo')` is converted to named imports.
+///  - `module.exports = foo` and `exports.foo = foo` are converted if
+///    `module` and `exports` are not used elsewhere.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// const foo = require('foo');
+/// const { bar } = require('bar');
+///
+/// exports.baz = foo(bar);
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// import foo from 'foo';
+/// import { bar } from 'bar';
+///
+/// const _baz = foo(bar);
+/// export { _baz as baz };
+/// ```
+pub fn cjs_to_esm() -> impl Pass {
+    CjsToEsm
+}
+
+struct CjsToEsm;
+
+enum ExportTarget {
+    /// `module.exports = ...`
+    Module,
+    /// `exports.foo = ...` or `module.exports.foo = ...`
+    Named(JsWord),
+}
+
+impl Fold<Module> for CjsToEsm {
+    fn fold(&mut self, mut module: Module) -> Module {
+        let mut usage = Usage::default();
+        module.visit_with(&mut usage);
+
+        let exports = convertible_exports(&module.body, &usage);
+
+        let mut in_prologue = true;
+        let mut body = Vec::with_capacity(module.body.len());
+
+        for item in module.body {
+            let stmt = match item {
+                ModuleItem::Stmt(stmt) => stmt,
+                ModuleItem::ModuleDecl(ModuleDecl::Import(..)) => {
+                    body.push(item);
+                    continue;
+                }
+                _ => {
+                    in_prologue = false;
+                    body.push(item);
+                    continue;
+                }
+            };
+
+            if in_prologue && is_directive(&stmt) {
+                body.push(ModuleItem::Stmt(stmt));
+                continue;
+            }
+
+            if in_prologue && !usage.declares_require {
+                if let Some(imports) = to_imports(&stmt) {
+                    body.extend(
+                        imports
+                            .into_iter()
+                            .map(|import| ModuleItem::ModuleDecl(ModuleDecl::Import(import))),
+                    );
+                    continue;
+                }
+            }
+            in_prologue = false;
+
+            if exports {
+                match stmt {
+                    Stmt::Expr(ExprStmt {
+                        span,
+                        expr: box Expr::Assign(assign),
+                    }) if assign.op == op!("=") && export_target(&assign.left).is_some() => {
+                        let target = export_target(&assign.left).unwrap();
+                        body.extend(to_export(span, target, assign.right));
+                        continue;
+                    }
+                    _ => {}
+                }
+            }
+
+            body.push(ModuleItem::Stmt(stmt));
+        }
+
+        module.body = body;
+
+        module.visit_with(&mut Reporter);
+
+        module
+    }
+}
+
+fn is_directive(stmt: &Stmt) -> bool {
+    match *stmt {
+        Stmt::Expr(ExprStmt {
+            expr: box Expr::Lit(Lit::Str(..)),
+            ..
+        }) => true,
+        _ => false,
+    }
+}
+
+/// Returns `Some(src)` for `require('src')`.
+fn require_src(e: &Expr) -> Option<&Str> {
+    match *e {
+        Expr::Call(CallExpr {
+            callee: ExprOrSuper::Expr(box Expr::Ident(ref callee)),
+            ref args,
+            ..
+        }) if callee.sym == *"require" && args.len() == 1 => match args[0] {
+            ExprOrSpread {
+                spread: None,
+                expr: box Expr::Lit(Lit::Str(ref src)),
+            } => Some(src),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Converts `const foo = require('foo')`, `const { foo } = require('foo')`
+/// and `require('foo')` to imports.
+fn to_imports(stmt: &Stmt) -> Option<Vec<ImportDecl>> {
+    match *stmt {
+        Stmt::Expr(ExprStmt { span, ref expr }) => {
+            let src = require_src(expr)?;
+            Some(vec![ImportDecl {
+                span,
+                specifiers: vec![],
+                src: src.clone(),
+            }])
+        }
+
+        Stmt::Decl(Decl::Var(VarDecl {
+            span,
+            kind: VarDeclKind::Const,
+            ref decls,
+            ..
+        })) => decls
+            .iter()
+            .map(|decl| {
+                let src = require_src(decl.init.as_ref()?)?;
+                let specifiers = match decl.name {
+                    Pat::Ident(ref local) => vec![ImportSpecifier::Default(ImportDefault {
+                        span: local.span,
+                        local: local.clone(),
+                    })],
+                    Pat::Object(ObjectPat { ref props, .. }) => props
+                        .iter()
+                        .map(|prop| match *prop {
+                            ObjectPatProp::Assign(AssignPatProp {
+                                span,
+                                ref key,
+                                value: None,
+                            }) => Some(ImportSpecifier::Specific(ImportSpecific {
+                                span,
+                                local: key.clone(),
+                                imported: None,
+                            })),
+                            ObjectPatProp::KeyValue(KeyValuePatProp {
+                                ref key,
+                                value: box Pat::Ident(ref local),
+                            }) => {
+                                let imported = match *key {
+                                    PropName::Ident(ref i) => i.clone(),
+                                    PropName::Str(ref s) => Ident::new(s.value.clone(), s.span),
+                                    _ => return None,
+                                };
+
+                                Some(ImportSpecifier::Specific(ImportSpecific {
+                                    span: prop.span(),
+                                    local: local.clone(),
+                                    imported: Some(imported),
+                                }))
+                            }
+                            _ => None,
+                        })
+                        .collect::<Option<_>>()?,
+                    _ => return None,
+                };
+
+                Some(ImportDecl {
+                    span,
+                    specifiers,
+                    src: src.clone(),
+                })
+            })
+            .collect(),
+
+        _ => None,
+    }
+}
+
+fn export_target(left: &PatOrExpr) -> Option<ExportTarget> {
+    let left = match *left {
+        PatOrExpr::Expr(ref e) => &**e,
+        PatOrExpr::Pat(box Pat::Expr(ref e)) => &**e,
+        _ => return None,
+    };
+
+    let (obj, prop) = match *left {
+        Expr::Member(MemberExpr {
+            obj: ExprOrSuper::Expr(ref obj),
+            ref prop,
+            computed,
+            ..
+        }) => {
+            let prop = match **prop {
+                Expr::Ident(ref i) if !computed => i.sym.clone(),
+                Expr::Lit(Lit::Str(ref s)) if computed => s.value.clone(),
+                _ => return None,
+            };
+            // `exports['foo-bar']` can't be converted to a binding.
+            if prop != js_word!("default") && !is_valid_binding(&prop) {
+                return None;
+            }
+            (&**obj, prop)
+        }
+        _ => return None,
+    };
+
+    match *obj {
+        Expr::Ident(ref i) if i.sym == *"module" && prop == *"exports" => {
+            Some(ExportTarget::Module)
+        }
+        Expr::Ident(ref i) if i.sym == *"exports" => Some(ExportTarget::Named(prop)),
+        _ => match export_target(&PatOrExpr::Expr(box obj.clone())) {
+            Some(ExportTarget::Module) => Some(ExportTarget::Named(prop)),
+            _ => None,
+        },
+    }
+}
+
+fn is_valid_binding(name: &JsWord) -> bool {
+    is_valid_ident(name)
+        && !name.is_reserved_for_es3()
+        && !["let", "yield", "await"].contains(&&**name)
+}
+
+/// Returns true if every use of `module` and `exports` is the left hand side
+/// of a top-level assignment which can be converted to an export.
+fn convertible_exports(items: &[ModuleItem], usage: &Usage) -> bool {
+    if usage.declares_exports {
+        return false;
+    }
+
+    let mut module_exports = 0;
+    let mut names: Vec<JsWord> = vec![];
+    let (mut module_refs, mut exports_refs) = (0, 0);
+
+    for item in items {
+        let assign = match *item {
+            ModuleItem::Stmt(Stmt::Expr(ExprStmt {
+                expr: box Expr::Assign(ref assign),
+                ..
+            })) if assign.op == op!("=") => assign,
+            _ => continue,
+        };
+
+        match export_target(&assign.left) {
+            Some(ExportTarget::Module) => {
+                module_exports += 1;
+                module_refs += 1;
+            }
+            Some(ExportTarget::Named(name)) => {
+                if names.contains(&name) {
+                    return false;
+                }
+                names.push(name);
+
+                let mut v = Usage::default();
+                assign.left.visit_with(&mut v);
+                module_refs += v.module;
+                exports_refs += v.exports;
+            }
+            None => {}
+        }
+    }
+
+    // `module.exports = foo` overwrites `exports.bar = baz`
+    if module_exports > 1 || (module_exports == 1 && !names.is_empty()) {
+        return false;
+    }
+    if module_exports == 1 && names.contains(&js_word!("default")) {
+        return false;
+    }
+
+    module_refs == usage.module && exports_refs == usage.exports
+}
+
+fn to_export(span: Span, target: ExportTarget, value: Box<Expr>) -> Vec<ModuleItem> {
+    let name = match target {
+        ExportTarget::Named(ref name) if *name != js_word!("default") => name.clone(),
+        _ => {
+            return vec![ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(
+                ExportDefaultExpr { span, expr: value },
+            ))]
+        }
+    };
+
+    let local = private_ident!(format!("_{}", name));
+
+    vec![
+        ModuleItem::Stmt(Stmt::Decl(Decl::Var(VarDecl {
+            span,
+            kind: VarDeclKind::Const,
+            declare: false,
+            decls: vec![VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(local.clone()),
+                init: Some(value),
+                definite: false,
+            }],
+        }))),
+        ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+            span,
+            specifiers: vec![ExportSpecifier::Named(NamedExportSpecifier {
+                span,
+                orig: local,
+                exported: Some(quote_ident!(name)),
+            })],
+            src: None,
+        })),
+    ]
+}
+
+/// Counts references to `module` and `exports`.
+#[derive(Default)]
+struct Usage {
+    module: usize,
+    exports: usize,
+    declares_require: bool,
+    declares_exports: bool,
+}
+
+impl Usage {
+    fn declare(&mut self, i: &Ident) {
+        match &*i.sym {
+            "require" => self.declares_require = true,
+            "module" | "exports" => self.declares_exports = true,
+            _ => {}
+        }
+    }
+
+    fn reference(&mut self, i: &Ident) {
+        match &*i.sym {
+            "module" => self.module += 1,
+            "exports" => self.exports += 1,
+            _ => {}
+        }
+    }
+}
+
+impl Visit<Expr> for Usage {
+    fn visit(&mut self, e: &Expr) {
+        if let Expr::Ident(ref i) = *e {
+            self.reference(i);
+        }
+        e.visit_children(self)
+    }
+}
+
+impl Visit<MemberExpr> for Usage {
+    fn visit(&mut self, e: &MemberExpr) {
+        e.obj.visit_with(self);
+        if e.computed {
+            e.prop.visit_with(self);
+        }
+    }
+}
+
+impl Visit<Prop> for Usage {
+    fn visit(&mut self, p: &Prop) {
+        if let Prop::Shorthand(ref i) = *p {
+            self.reference(i);
+        }
+        p.visit_children(self)
+    }
+}
+
+impl Visit<Pat> for Usage {
+    fn visit(&mut self, p: &Pat) {
+        if let Pat::Ident(ref i) = *p {
+            self.declare(i);
+        }
+        p.visit_children(self)
+    }
+}
+
+impl Visit<FnDecl> for Usage {
+    fn visit(&mut self, f: &FnDecl) {
+        self.declare(&f.ident);
+        f.visit_children(self)
+    }
+}
+
+impl Visit<ClassDecl> for Usage {
+    fn visit(&mut self, c: &ClassDecl) {
+        self.declare(&c.ident);
+        c.visit_children(self)
+    }
+}
+
+/// Warns about patterns which are not converted.
+struct Reporter;
+
+impl Reporter {
+    fn report(&self, span: Span, msg: &str) {
+        HANDLER.with(|handler| handler.struct_span_warn(span, msg).emit());
+    }
+}
+
+impl Visit<Expr> for Reporter {
+    fn visit(&mut self, e: &Expr) {
+        match *e {
+            Expr::Call(CallExpr {
+                callee: ExprOrSuper::Expr(box Expr::Ident(ref callee)),
+                span,
+                ..
+            }) if callee.sym == *"require" => {
+                self.report(span, "cjs_to_esm: this require call cannot be converted to an import")
+            }
+
+            Expr::Assign(AssignExpr { span, ref left, .. }) if export_target(left).is_some() => {
+                self.report(span, "cjs_to_esm: this assignment cannot be converted to an export")
+            }
+
+            _ => {}
+        }
+
+        e.visit_children(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn syntax() -> ::swc_ecma_parser::Syntax {
+        Default::default()
+    }
+
+    test!(
+        syntax(),
+        |_| cjs_to_esm(),
+        require_default,
+        "'use strict';
+const foo = require('foo');
+require('bar');
+foo();",
+        "'use strict';
+import foo from 'foo';
+import 'bar';
+foo();"
+    );
+
+    test!(
+        syntax(),
+        |_| cjs_to_esm(),
+        require_destructuring,
+        "const { a, b: c, 'd': e } = require('foo'), bar = require('bar');",
+        "import { a, b as c, d as e } from 'foo';
+import bar from 'bar';"
+    );
+
+    test!(
+        syntax(),
+        |_| cjs_to_esm(),
+        require_after_code,
+        "const foo = require('foo');
+init();
+const bar = require('bar');",
+        "import foo from 'foo';
+init();
+const bar = require('bar');"
+    );
+
+    test!(
+        syntax(),
+        |_| cjs_to_esm(),
+        require_not_const,
+        "let foo = require('foo');
+const { bar = 1 } = require('bar');",
+        "let foo = require('foo');
+const { bar = 1 } = require('bar');"
+    );
+
+    test!(
+        syntax(),
+        |_| cjs_to_esm(),
+        require_conditional,
+        "const fs = require('fs');
+if (process.env.DEBUG) {
+    require('debug');
+}",
+        "import fs from 'fs';
+if (process.env.DEBUG) {
+    require('debug');
+}"
+    );
+
+    test!(
+        syntax(),
+        |_| cjs_to_esm(),
+        module_exports,
+        "function foo() {}
+module.exports = foo;",
+        "function foo() {}
+export default foo;"
+    );
+
+    test!(
+        syntax(),
+        |_| cjs_to_esm(),
+        named_exports,
+        "const foo = 1;
+exports.foo = foo;
+module.exports.bar = foo + 1;
+exports['baz'] = 3;",
+        "const foo = 1;
+const _foo = foo;
+export { _foo as foo };
+const _bar = foo + 1;
+export { _bar as bar };
+const _baz = 3;
+export { _baz as baz };"
+    );
+
+    test!(
+        syntax(),
+        |_| cjs_to_esm(),
+        exports_used_elsewhere,
+        "exports.foo = 1;
+exports.bar = exports.foo + 1;",
+        "exports.foo = 1;
+exports.bar = exports.foo + 1;"
+    );
+
+    test!(
+        syntax(),
+        |_| cjs_to_esm(),
+        exports_overwritten,
+        "exports.foo = 1;
+module.exports = {};",
+        "exports.foo = 1;
+module.exports = {};"
+    );
+
+    test!(
+        syntax(),
+        |_| cjs_to_esm(),
+        exports_invalid_names,
+        "exports['foo-bar'] = 1;
+exports.class = 2;
+exports.foo = 3;",
+        "exports['foo-bar'] = 1;
+exports.class = 2;
+exports.foo = 3;"
+    );
+
+    test!(
+        syntax(),
+        |_| cjs_to_esm(),
+        exports_declared,
+        "var exports = {};
+exports.foo = 1;",
+        "var exports = {};
+exports.foo = 1;"
+    );
+
+    #[test]
+    fn report_unconverted() {
+        let stderr = ::testing::run_test::<_, ()>(false, |cm, handler| {
+            HANDLER.set(handler, || {
+                let mut tester = crate::tests::Tester {
+                    cm,
+                    handler,
+                    comments: Default::default(),
+                };
+                tester.apply_transform(
+                    cjs_to_esm(),
+                    "input.js",
+                    syntax(),
+                    "if (cond) { require('foo'); }",
+                )?;
+
+                Err(())
+            })
+        })
+        .unwrap_err()
+        .to_string();
+
+        assert!(
+            stderr.contains("cannot be converted to an import"),
+            "{}",
+            stderr
+        );
+    }
+}
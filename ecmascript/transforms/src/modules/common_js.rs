@@ -5,7 +5,7 @@ use super::util::{
 };
 use crate::{
     pass::Pass,
-    util::{var::VarCollector, DestructuringFinder, ExprFactory},
+    util::{DestructuringFinder, ExprFactory},
 };
 use ast::*;
 use fxhash::FxHashSet;
@@ -40,6 +40,8 @@ impl Fold<Vec<ModuleItem>> for CommonJs {
         let mut initialized = FxHashSet::default();
         let mut export_alls = vec![];
 
+        self.scope.collect_exported_vars(&items);
+
         for item in items {
             self.in_top_level = true;
 
@@ -149,21 +151,12 @@ impl Fold<Vec<ModuleItem>> for CommonJs {
                                 var.clone().fold_with(self),
                             ))));
 
-                            var.decls.visit_with(&mut VarCollector {
-                                to: &mut self.scope.declared_vars,
-                            });
-
                             let mut found: Vec<Ident> = vec![];
                             for decl in var.decls {
                                 let mut v = DestructuringFinder { found: &mut found };
                                 decl.visit_with(&mut v);
 
                                 for ident in found.drain(..) {
-                                    self.scope
-                                        .exported_vars
-                                        .entry((ident.sym.clone(), ident.span.ctxt()))
-                                        .or_default()
-                                        .push((ident.sym.clone(), ident.span.ctxt()));
                                     init_export!(ident.sym);
 
                                     extra_stmts.push(
@@ -299,22 +292,6 @@ impl Fold<Vec<ModuleItem>> for CommonJs {
                             {
                                 let is_import_default = orig.sym == js_word!("default");
 
-                                let key = (orig.sym.clone(), orig.span.ctxt());
-                                if self.scope.declared_vars.contains(&key) {
-                                    self.scope
-                                        .exported_vars
-                                        .entry(key.clone())
-                                        .or_default()
-                                        .push(
-                                            exported
-                                                .clone()
-                                                .map(|i| (i.sym.clone(), i.span.ctxt()))
-                                                .unwrap_or_else(|| {
-                                                    (orig.sym.clone(), orig.span.ctxt())
-                                                }),
-                                        );
-                                }
-
                                 if let Some(ref src) = export.src {
                                     if is_import_default {
                                         self.scope
@@ -622,33 +599,6 @@ impl Fold<Prop> for CommonJs {
     }
 }
 
-impl Fold<VarDecl> for CommonJs {
-    ///
-    /// - collects all declared variables for let and var.
-    fn fold(&mut self, var: VarDecl) -> VarDecl {
-        if var.kind != VarDeclKind::Const {
-            var.decls.visit_with(&mut VarCollector {
-                to: &mut self.scope.declared_vars,
-            });
-        }
-
-        VarDecl {
-            decls: var.decls.fold_with(self),
-            ..var
-        }
-    }
-}
-
-impl Fold<FnDecl> for CommonJs {
-    fn fold(&mut self, node: FnDecl) -> FnDecl {
-        self.scope
-            .declared_vars
-            .push((node.ident.sym.clone(), node.ident.span.ctxt()));
-
-        node.fold_children(self)
-    }
-}
-
 impl ModulePass for CommonJs {
     fn config(&self) -> &Config {
         &self.config
@@ -7,7 +7,7 @@ use inflector::Inflector;
 use serde::{Deserialize, Serialize};
 use std::iter;
 use swc_atoms::{js_word, JsWord};
-use swc_common::{FoldWith, Mark, Span, SyntaxContext, VisitWith, DUMMY_SP};
+use swc_common::{FoldWith, Mark, Span, SyntaxContext, Visit, VisitWith, DUMMY_SP};
 
 pub(super) trait ModulePass {
     fn config(&self) -> &Config;
@@ -96,9 +96,6 @@ pub(super) struct Scope {
     ///   -> `{foo: ('bar', default)}`
     pub idents: HashMap<(JsWord, SyntaxContext), (JsWord, JsWord)>,
 
-    /// Declared variables except const.
-    pub declared_vars: Vec<(JsWord, SyntaxContext)>,
-
     /// Maps of exported variables.
     ///
     ///
@@ -346,6 +343,80 @@ impl Scope {
         }
     }
 
+    /// Collects local bindings exported by `items` into `exported_vars`.
+    ///
+    /// This should be done before folding, because assignments to an exported
+    /// binding can precede the export statement or be nested in the exported
+    /// declaration itself.
+    pub fn collect_exported_vars(&mut self, items: &[ModuleItem]) {
+        let mut declared: HashSet<(JsWord, SyntaxContext)> = HashSet::default();
+        let mut exported: Vec<((JsWord, SyntaxContext), (JsWord, SyntaxContext))> = vec![];
+
+        for item in items {
+            let (decl, is_exported) = match *item {
+                ModuleItem::Stmt(Stmt::Decl(ref decl)) => (decl, false),
+                // `var`s nested in blocks, loops and `if` bodies.
+                ModuleItem::Stmt(ref stmt) => {
+                    stmt.visit_with(&mut NestedVarFinder {
+                        declared: &mut declared,
+                    });
+                    continue;
+                }
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { ref decl, .. })) => {
+                    (decl, true)
+                }
+                ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+                    ref specifiers,
+                    src: None,
+                    ..
+                })) => {
+                    for s in specifiers {
+                        if let ExportSpecifier::Named(NamedExportSpecifier {
+                            ref orig,
+                            exported: ref e,
+                            ..
+                        }) = *s
+                        {
+                            let e = e.as_ref().unwrap_or(orig);
+                            exported.push((
+                                (orig.sym.clone(), orig.span.ctxt()),
+                                (e.sym.clone(), e.span.ctxt()),
+                            ));
+                        }
+                    }
+                    continue;
+                }
+                _ => continue,
+            };
+
+            let ids: Vec<Ident> = match *decl {
+                Decl::Class(ClassDecl { ref ident, .. }) | Decl::Fn(FnDecl { ref ident, .. }) => {
+                    vec![ident.clone()]
+                }
+                Decl::Var(VarDecl { ref decls, .. }) => {
+                    let mut found = vec![];
+                    decls.visit_with(&mut DestructuringFinder { found: &mut found });
+                    found
+                }
+                _ => continue,
+            };
+
+            for i in ids {
+                let id = (i.sym.clone(), i.span.ctxt());
+                if is_exported {
+                    exported.push((id.clone(), id.clone()));
+                }
+                declared.insert(id);
+            }
+        }
+
+        for (local, name) in exported {
+            if declared.contains(&local) {
+                self.exported_vars.entry(local).or_default().push(name);
+            }
+        }
+    }
+
     pub(super) fn fold_shorthand_prop(
         folder: &mut impl ModulePass,
         top_level: bool,
@@ -782,6 +853,36 @@ pub(super) fn make_descriptor(get_expr: Box<Expr>) -> ObjectLit {
 }
 
 /// Private `_exports` ident.
+/// Finds `var` declarations, without entering functions.
+struct NestedVarFinder<'a> {
+    declared: &'a mut HashSet<(JsWord, SyntaxContext)>,
+}
+
+impl Visit<VarDecl> for NestedVarFinder<'_> {
+    fn visit(&mut self, var: &VarDecl) {
+        if var.kind == VarDeclKind::Var {
+            let mut found: Vec<Ident> = vec![];
+            var.decls.visit_with(&mut DestructuringFinder { found: &mut found });
+            self.declared.extend(found.into_iter().map(|i| (i.sym, i.span.ctxt())));
+        }
+
+        var.decls.visit_with(self);
+    }
+}
+
+macro_rules! noop {
+    ($T:tt) => {
+        impl Visit<$T> for NestedVarFinder<'_> {
+            fn visit(&mut self, _: &$T) {}
+        }
+    };
+}
+noop!(Function);
+noop!(ArrowExpr);
+noop!(Constructor);
+noop!(GetterProp);
+noop!(SetterProp);
+
 pub(super) struct Exports(pub Ident);
 
 impl Default for Exports {
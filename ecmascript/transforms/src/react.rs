@@ -1,17 +1,23 @@
 pub use self::{
-    display_name::display_name,
-    jsx::{jsx, Options},
-    jsx_self::jsx_self,
+    constant_elements::constant_elements,
+    display_name::{display_name, Config as DisplayNameConfig},
+    inline_elements::inline_elements,
+    jsx::{jsx, Options, Runtime},
+    jsx_self::{jsx_self, DevOptions},
     jsx_src::jsx_src,
+    strip_dev_props::strip_dev_props,
 };
 use crate::pass::Pass;
 use std::sync::Arc;
 use swc_common::{chain, SourceMap};
 
+mod constant_elements;
 mod display_name;
+mod inline_elements;
 mod jsx;
 mod jsx_self;
 mod jsx_src;
+mod strip_dev_props;
 
 /// `@babel/preset-react`
 ///
@@ -20,9 +26,9 @@ pub fn react(cm: Arc<SourceMap>, options: Options) -> impl Pass {
     let Options { development, .. } = options;
 
     chain!(
+        jsx_src(development, cm.clone(), Default::default()),
+        jsx_self(development, Default::default()),
         jsx(options),
-        display_name(),
-        jsx_src(development, cm),
-        jsx_self(development)
+        display_name(cm, Default::default()),
     )
 }
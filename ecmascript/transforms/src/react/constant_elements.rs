@@ -0,0 +1,678 @@
+use crate::{
+    pass::Pass,
+    util::{find_ids, id, prepend, Id, StmtLike},
+};
+use ast::*;
+use fxhash::{FxHashMap, FxHashSet};
+use std::mem;
+use swc_atoms::js_word;
+use swc_common::{Fold, FoldWith, Visit, VisitWith, DUMMY_SP};
+
+#[cfg(test)]
+mod tests;
+
+/// `@babel/plugin-transform-react-constant-elements`
+///
+/// Hoists constant elements to the highest scope where all of their bindings
+/// are available, so that react can reuse them between renders.
+///
+/// An element is constant if it only references bindings which are never
+/// reassigned, and it does not contain `this`, a `ref` attribute or a spread
+/// of anything other than an identifier. Elements are hoisted only out of a
+/// function, and never above a declaration they use.
+///
+/// This pass works on jsx elements, so it should run before `jsx`.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// function App() {
+///   return <div><Spinner /></div>;
+/// }
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// var _ref = <div><Spinner /></div>;
+///
+/// function App() {
+///   return _ref;
+/// }
+/// ```
+pub fn constant_elements() -> impl Pass {
+    ConstantElements::default()
+}
+
+#[derive(Default)]
+struct ConstantElements {
+    scopes: Vec<Scope>,
+    reassigned: FxHashSet<Id>,
+}
+
+#[derive(Default)]
+struct Scope {
+    /// `None` if the binding is initialized before any statement of the scope
+    /// runs. Otherwise, index of the statement declaring the binding.
+    bindings: FxHashMap<Id, Option<usize>>,
+    /// Index of the statement being folded. `None` if the scope does not have
+    /// statements to hoist elements into.
+    cur: Option<usize>,
+    /// True for the scope of a function.
+    is_fn: bool,
+    /// Elements to be declared before the current statement.
+    hoisted: Vec<VarDeclarator>,
+    /// Elements which only use bindings available from the start of the
+    /// scope. These are declared at the top of the scope, because a function
+    /// declaration may be called before the current statement runs.
+    hoisted_top: Vec<VarDeclarator>,
+}
+
+impl Scope {
+    fn new<I>(is_fn: bool, ids: I) -> Self
+    where
+        I: IntoIterator<Item = Id>,
+    {
+        Scope {
+            bindings: ids.into_iter().map(|id| (id, None)).collect(),
+            is_fn,
+            ..Default::default()
+        }
+    }
+
+    fn collect<T>(&mut self, stmts: &[T])
+    where
+        T: StmtLike + VisitWith<BindingCollector>,
+    {
+        for (i, stmt) in stmts.iter().enumerate() {
+            // Functions are initialized before the body runs.
+            let pos = match stmt.as_stmt() {
+                Some(&Stmt::Decl(Decl::Fn(..))) => None,
+                _ => Some(i),
+            };
+
+            let mut v = BindingCollector {
+                pos,
+                bindings: vec![],
+            };
+            stmt.visit_with(&mut v);
+            self.bindings.extend(v.bindings);
+        }
+    }
+}
+
+impl ConstantElements {
+    fn fold_in<T>(&mut self, scope: Scope, node: T) -> T
+    where
+        T: FoldWith<Self>,
+    {
+        self.scopes.push(scope);
+        let node = node.fold_children(self);
+        self.scopes.pop();
+        node
+    }
+
+    fn fold_stmts<T>(&mut self, stmts: Vec<T>) -> Vec<T>
+    where
+        T: StmtLike + FoldWith<Self>,
+    {
+        let mut buf = Vec::with_capacity(stmts.len());
+
+        for (i, stmt) in stmts.into_iter().enumerate() {
+            self.scopes.last_mut().unwrap().cur = Some(i);
+            let stmt = stmt.fold_with(self);
+
+            let decls = mem::replace(&mut self.scopes.last_mut().unwrap().hoisted, vec![]);
+            if !decls.is_empty() {
+                buf.push(T::from_stmt(var_decl(decls)));
+            }
+            buf.push(stmt);
+        }
+
+        buf
+    }
+
+    fn fold_fn_body(&mut self, params: Vec<Id>, body: BlockStmt) -> BlockStmt {
+        let mut scope = Scope::new(true, params);
+        scope.collect(&body.stmts);
+
+        self.scopes.push(scope);
+        let mut stmts = self.fold_stmts(body.stmts);
+        let scope = self.scopes.pop().unwrap();
+
+        if !scope.hoisted_top.is_empty() {
+            prepend(&mut stmts, var_decl(scope.hoisted_top));
+        }
+
+        BlockStmt { stmts, ..body }
+    }
+
+    /// Hoists `el` and returns the identifier of the hoisted element, or
+    /// returns `el` if it can't be hoisted.
+    fn hoist(&mut self, el: Box<JSXElement>) -> Result<Ident, Box<JSXElement>> {
+        let mut refs = vec![];
+        if self.scopes.is_empty() || !is_constant_element(&el, &mut refs) {
+            return Err(el);
+        }
+
+        let mut target = 0;
+        // Position of the last binding declared in the target scope.
+        let mut last_pos = None;
+        for r in &refs {
+            if self.reassigned.contains(r) {
+                return Err(el);
+            }
+
+            let found = self
+                .scopes
+                .iter()
+                .enumerate()
+                .rev()
+                .find_map(|(i, scope)| scope.bindings.get(r).map(|pos| (i, *pos)));
+            let (i, pos) = match found {
+                Some(v) => v,
+                // Global
+                None => continue,
+            };
+
+            // Used before declaration.
+            match (pos, self.scopes[i].cur) {
+                (None, _) => {}
+                (Some(pos), Some(cur)) if pos < cur => {}
+                _ => return Err(el),
+            }
+
+            if i > target {
+                target = i;
+                last_pos = pos;
+            } else if i == target {
+                last_pos = last_pos.max(pos);
+            }
+        }
+
+        if self.scopes[target].cur.is_none()
+            || !self.scopes[target + 1..].iter().any(|scope| scope.is_fn)
+        {
+            return Err(el);
+        }
+
+        let ident = private_ident!("_ref");
+        let decl = VarDeclarator {
+            span: DUMMY_SP,
+            name: Pat::Ident(ident.clone()),
+            init: Some(box Expr::JSXElement(el)),
+            definite: false,
+        };
+        match last_pos {
+            None => self.scopes[target].hoisted_top.push(decl),
+            Some(..) => self.scopes[target].hoisted.push(decl),
+        }
+
+        Ok(ident)
+    }
+}
+
+impl Fold<Module> for ConstantElements {
+    fn fold(&mut self, module: Module) -> Module {
+        let mut v = ReassignFinder {
+            found: Default::default(),
+        };
+        module.visit_with(&mut v);
+        self.reassigned = v.found;
+
+        let mut scope = Scope::default();
+        scope.collect(&module.body);
+
+        self.scopes.push(scope);
+        let mut body = self.fold_stmts(module.body);
+        let scope = self.scopes.pop().unwrap();
+
+        if !scope.hoisted_top.is_empty() {
+            // Imports are available from the start, so declare elements after
+            // them.
+            let idx = body
+                .iter()
+                .position(|item| match *item {
+                    ModuleItem::ModuleDecl(ModuleDecl::Import(..)) => false,
+                    ModuleItem::Stmt(Stmt::Expr(ExprStmt {
+                        expr: box Expr::Lit(Lit::Str(..)),
+                        ..
+                    })) => false,
+                    _ => true,
+                })
+                .unwrap_or(body.len());
+            body.insert(idx, ModuleItem::Stmt(var_decl(scope.hoisted_top)));
+        }
+
+        Module { body, ..module }
+    }
+}
+
+impl Fold<Script> for ConstantElements {
+    fn fold(&mut self, script: Script) -> Script {
+        let mut v = ReassignFinder {
+            found: Default::default(),
+        };
+        script.visit_with(&mut v);
+        self.reassigned = v.found;
+
+        let mut scope = Scope::default();
+        scope.collect(&script.body);
+
+        self.scopes.push(scope);
+        let mut body = self.fold_stmts(script.body);
+        let scope = self.scopes.pop().unwrap();
+
+        if !scope.hoisted_top.is_empty() {
+            prepend(&mut body, var_decl(scope.hoisted_top));
+        }
+
+        Script { body, ..script }
+    }
+}
+
+impl Fold<Function> for ConstantElements {
+    fn fold(&mut self, f: Function) -> Function {
+        let decorators = f.decorators.fold_with(self);
+        let params = f.params.fold_with(self);
+        let body = f
+            .body
+            .map(|body| self.fold_fn_body(find_ids(&params), body));
+
+        Function {
+            decorators,
+            params,
+            body,
+            ..f
+        }
+    }
+}
+
+impl Fold<ArrowExpr> for ConstantElements {
+    fn fold(&mut self, f: ArrowExpr) -> ArrowExpr {
+        let params = f.params.fold_with(self);
+        let body = match f.body {
+            BlockStmtOrExpr::BlockStmt(body) => {
+                BlockStmtOrExpr::BlockStmt(self.fold_fn_body(find_ids(&params), body))
+            }
+            BlockStmtOrExpr::Expr(expr) => {
+                self.scopes.push(Scope::new(true, find_ids(&params)));
+                let expr = expr.fold_with(self);
+                self.scopes.pop();
+
+                BlockStmtOrExpr::Expr(expr)
+            }
+        };
+
+        ArrowExpr { params, body, ..f }
+    }
+}
+
+impl Fold<Constructor> for ConstantElements {
+    fn fold(&mut self, c: Constructor) -> Constructor {
+        let params = c.params.fold_with(self);
+        let body = c
+            .body
+            .map(|body| self.fold_fn_body(find_ids(&params), body));
+
+        Constructor { params, body, ..c }
+    }
+}
+
+impl Fold<GetterProp> for ConstantElements {
+    fn fold(&mut self, p: GetterProp) -> GetterProp {
+        let key = p.key.fold_with(self);
+        let body = p.body.map(|body| self.fold_fn_body(vec![], body));
+
+        GetterProp { key, body, ..p }
+    }
+}
+
+impl Fold<SetterProp> for ConstantElements {
+    fn fold(&mut self, p: SetterProp) -> SetterProp {
+        let key = p.key.fold_with(self);
+        let param = p.param.fold_with(self);
+        let body = p.body.map(|body| self.fold_fn_body(find_ids(&param), body));
+
+        SetterProp {
+            key,
+            param,
+            body,
+            ..p
+        }
+    }
+}
+
+impl Fold<FnExpr> for ConstantElements {
+    fn fold(&mut self, f: FnExpr) -> FnExpr {
+        match f.ident {
+            Some(ref ident) => {
+                let scope = Scope::new(false, vec![id(ident)]);
+                self.fold_in(scope, f)
+            }
+            None => f.fold_children(self),
+        }
+    }
+}
+
+impl Fold<ClassExpr> for ConstantElements {
+    fn fold(&mut self, c: ClassExpr) -> ClassExpr {
+        match c.ident {
+            Some(ref ident) => {
+                let scope = Scope::new(false, vec![id(ident)]);
+                self.fold_in(scope, c)
+            }
+            None => c.fold_children(self),
+        }
+    }
+}
+
+impl Fold<Expr> for ConstantElements {
+    fn fold(&mut self, e: Expr) -> Expr {
+        match e {
+            Expr::JSXElement(el) => match self.hoist(el) {
+                Ok(ident) => Expr::Ident(ident),
+                Err(el) => Expr::JSXElement(el.fold_with(self)),
+            },
+            _ => e.fold_children(self),
+        }
+    }
+}
+
+impl Fold<JSXElementChild> for ConstantElements {
+    fn fold(&mut self, c: JSXElementChild) -> JSXElementChild {
+        match c {
+            JSXElementChild::JSXElement(el) => match self.hoist(el) {
+                Ok(ident) => JSXElementChild::JSXExprContainer(JSXExprContainer {
+                    span: DUMMY_SP,
+                    expr: JSXExpr::Expr(box Expr::Ident(ident)),
+                }),
+                Err(el) => JSXElementChild::JSXElement(el.fold_with(self)),
+            },
+            _ => c.fold_children(self),
+        }
+    }
+}
+
+fn var_decl(decls: Vec<VarDeclarator>) -> Stmt {
+    Stmt::Decl(Decl::Var(VarDecl {
+        span: DUMMY_SP,
+        kind: VarDeclKind::Var,
+        declare: false,
+        decls,
+    }))
+}
+
+/// Returns false if `el` is not constant. Identifiers used by `el` are stored
+/// in `refs`.
+fn is_constant_element(el: &JSXElement, refs: &mut Vec<Id>) -> bool {
+    let name = match el.opening.name {
+        JSXElementName::Ident(ref i) => {
+            if i.sym == js_word!("this") {
+                return false;
+            }
+            // Lowercase names are converted to strings.
+            if !i.sym.starts_with(|c: char| c.is_ascii_lowercase()) {
+                refs.push(id(i));
+            }
+            true
+        }
+        JSXElementName::JSXMemberExpr(ref e) => is_constant_object(&e.obj, refs),
+        JSXElementName::JSXNamespacedName(..) => true,
+    };
+
+    name
+        && el.opening.attrs.iter().all(|a| is_constant_attr(a, refs))
+        && el.children.iter().all(|c| is_constant_child(c, refs))
+}
+
+fn is_constant_object(obj: &JSXObject, refs: &mut Vec<Id>) -> bool {
+    match *obj {
+        JSXObject::Ident(ref i) => {
+            if i.sym == js_word!("this") {
+                return false;
+            }
+            refs.push(id(i));
+            true
+        }
+        JSXObject::JSXMemberExpr(ref e) => is_constant_object(&e.obj, refs),
+    }
+}
+
+fn is_constant_attr(attr: &JSXAttrOrSpread, refs: &mut Vec<Id>) -> bool {
+    match *attr {
+        JSXAttrOrSpread::JSXAttr(ref a) => {
+            match a.name {
+                // Refs are mutated by react.
+                JSXAttrName::Ident(ref i) if i.sym == *"ref" => return false,
+                _ => {}
+            }
+
+            match a.value {
+                None | Some(JSXAttrValue::Lit(..)) => true,
+                Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+                    expr: JSXExpr::Expr(ref e),
+                    ..
+                })) => is_constant_expr(e, refs),
+                Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+                    expr: JSXExpr::JSXEmptyExpr(..),
+                    ..
+                })) => true,
+                Some(JSXAttrValue::JSXElement(ref el)) => is_constant_element(el, refs),
+                Some(JSXAttrValue::JSXFragment(ref f)) => {
+                    f.children.iter().all(|c| is_constant_child(c, refs))
+                }
+            }
+        }
+        JSXAttrOrSpread::SpreadElement(ref s) => match *s.expr {
+            Expr::Ident(ref i) => {
+                refs.push(id(i));
+                true
+            }
+            _ => false,
+        },
+    }
+}
+
+fn is_constant_child(c: &JSXElementChild, refs: &mut Vec<Id>) -> bool {
+    match *c {
+        JSXElementChild::JSXText(..) => true,
+        JSXElementChild::JSXExprContainer(JSXExprContainer {
+            expr: JSXExpr::Expr(ref e),
+            ..
+        }) => is_constant_expr(e, refs),
+        JSXElementChild::JSXExprContainer(JSXExprContainer {
+            expr: JSXExpr::JSXEmptyExpr(..),
+            ..
+        }) => true,
+        JSXElementChild::JSXElement(ref el) => is_constant_element(el, refs),
+        JSXElementChild::JSXFragment(ref f) => {
+            f.children.iter().all(|c| is_constant_child(c, refs))
+        }
+        JSXElementChild::JSXSpreadChild(..) => false,
+    }
+}
+
+fn is_constant_expr(e: &Expr, refs: &mut Vec<Id>) -> bool {
+    match *e {
+        // Regular expressions are mutable objects.
+        Expr::Lit(Lit::Regex(..)) => false,
+        Expr::Lit(..) => true,
+        Expr::Ident(ref i) => {
+            refs.push(id(i));
+            true
+        }
+        Expr::Tpl(ref t) => t.exprs.iter().all(|e| is_constant_expr(e, refs)),
+        Expr::Unary(UnaryExpr { op, ref arg, .. }) => {
+            op != op!("delete") && is_constant_expr(arg, refs)
+        }
+        Expr::Bin(BinExpr {
+            ref left,
+            ref right,
+            ..
+        }) => is_constant_expr(left, refs) && is_constant_expr(right, refs),
+        Expr::Cond(CondExpr {
+            ref test,
+            ref cons,
+            ref alt,
+            ..
+        }) => {
+            is_constant_expr(test, refs)
+                && is_constant_expr(cons, refs)
+                && is_constant_expr(alt, refs)
+        }
+        Expr::Paren(ParenExpr { ref expr, .. }) => is_constant_expr(expr, refs),
+        Expr::JSXElement(ref el) => is_constant_element(el, refs),
+        Expr::JSXFragment(ref f) => f.children.iter().all(|c| is_constant_child(c, refs)),
+        _ => false,
+    }
+}
+
+/// Collects bindings declared by a statement, excluding ones in nested
+/// functions.
+struct BindingCollector {
+    pos: Option<usize>,
+    bindings: Vec<(Id, Option<usize>)>,
+}
+
+impl BindingCollector {
+    fn add(&mut self, ids: Vec<Id>, pos: Option<usize>) {
+        self.bindings.extend(ids.into_iter().map(|id| (id, pos)));
+    }
+}
+
+impl Visit<VarDeclarator> for BindingCollector {
+    fn visit(&mut self, d: &VarDeclarator) {
+        let pos = self.pos;
+        self.add(find_ids(&d.name), pos);
+    }
+}
+
+impl Visit<FnDecl> for BindingCollector {
+    fn visit(&mut self, f: &FnDecl) {
+        let pos = self.pos;
+        self.add(vec![id(&f.ident)], pos);
+    }
+}
+
+impl Visit<ClassDecl> for BindingCollector {
+    fn visit(&mut self, c: &ClassDecl) {
+        let pos = self.pos;
+        self.add(vec![id(&c.ident)], pos);
+    }
+}
+
+impl Visit<ExportDefaultDecl> for BindingCollector {
+    fn visit(&mut self, d: &ExportDefaultDecl) {
+        let ident = match d.decl {
+            DefaultDecl::Class(ClassExpr {
+                ident: Some(ref ident),
+                ..
+            })
+            | DefaultDecl::Fn(FnExpr {
+                ident: Some(ref ident),
+                ..
+            }) => ident,
+            _ => return,
+        };
+
+        let pos = self.pos;
+        self.add(vec![id(ident)], pos);
+    }
+}
+
+impl Visit<CatchClause> for BindingCollector {
+    fn visit(&mut self, c: &CatchClause) {
+        let pos = self.pos;
+        self.add(find_ids(&c.param), pos);
+        c.body.visit_with(self);
+    }
+}
+
+impl Visit<ImportDecl> for BindingCollector {
+    /// Imports are available from the start.
+    fn visit(&mut self, i: &ImportDecl) {
+        for s in &i.specifiers {
+            let local = match *s {
+                ImportSpecifier::Specific(ImportSpecific { ref local, .. })
+                | ImportSpecifier::Default(ImportDefault { ref local, .. })
+                | ImportSpecifier::Namespace(ImportStarAs { ref local, .. }) => local,
+            };
+            self.add(vec![id(local)], None);
+        }
+    }
+}
+
+impl Visit<Expr> for BindingCollector {
+    fn visit(&mut self, _: &Expr) {}
+}
+
+impl Visit<Function> for BindingCollector {
+    fn visit(&mut self, _: &Function) {}
+}
+
+impl Visit<Class> for BindingCollector {
+    fn visit(&mut self, _: &Class) {}
+}
+
+/// Finds bindings which are assigned after declaration.
+struct ReassignFinder {
+    found: FxHashSet<Id>,
+}
+
+impl ReassignFinder {
+    fn add_pat(&mut self, pat: &Pat) {
+        match *pat {
+            Pat::Expr(box Expr::Ident(ref i)) => {
+                self.found.insert(id(i));
+            }
+            _ => self.found.extend(find_ids::<_, Id>(pat)),
+        }
+    }
+}
+
+impl Visit<AssignExpr> for ReassignFinder {
+    fn visit(&mut self, e: &AssignExpr) {
+        match e.left {
+            PatOrExpr::Pat(ref pat) => self.add_pat(pat),
+            PatOrExpr::Expr(box Expr::Ident(ref i)) => {
+                self.found.insert(id(i));
+            }
+            _ => {}
+        }
+
+        e.visit_children(self);
+    }
+}
+
+impl Visit<UpdateExpr> for ReassignFinder {
+    fn visit(&mut self, e: &UpdateExpr) {
+        if let Expr::Ident(ref i) = *e.arg {
+            self.found.insert(id(i));
+        }
+
+        e.visit_children(self);
+    }
+}
+
+impl Visit<ForInStmt> for ReassignFinder {
+    fn visit(&mut self, s: &ForInStmt) {
+        if let VarDeclOrPat::Pat(ref pat) = s.left {
+            self.add_pat(pat);
+        }
+
+        s.visit_children(self);
+    }
+}
+
+impl Visit<ForOfStmt> for ReassignFinder {
+    fn visit(&mut self, s: &ForOfStmt) {
+        if let VarDeclOrPat::Pat(ref pat) = s.left {
+            self.add_pat(pat);
+        }
+
+        s.visit_children(self);
+    }
+}
@@ -0,0 +1,162 @@
+use super::*;
+
+fn tr() -> impl Fold<Module> {
+    constant_elements()
+}
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |_| tr(),
+    hoist_out_of_component,
+    r#"import Spinner from "./spinner";
+function App() {
+    return <div className="loading"><Spinner /></div>;
+}"#,
+    r#"import Spinner from "./spinner";
+var _ref = <div className="loading"><Spinner /></div>;
+function App() {
+    return _ref;
+}"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |_| tr(),
+    non_constant,
+    r#"function App(props) {
+    return <Spinner size={props.size} />;
+}
+function Button({ label }) {
+    return <button>{label}</button>;
+}
+class Foo {
+    render() {
+        return <div>{this}</div>;
+    }
+}"#,
+    r#"function App(props) {
+    return <Spinner size={props.size} />;
+}
+function Button({ label }) {
+    return <button>{label}</button>;
+}
+class Foo {
+    render() {
+        return <div>{this}</div>;
+    }
+}"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |_| tr(),
+    hoist_constant_child,
+    r#"function List({ items }) {
+    return <ul>{items.map(item => <li key={item}><Icon /></li>)}</ul>;
+}"#,
+    r#"var _ref = <Icon />;
+function List({ items }) {
+    return <ul>{items.map(item => <li key={item}>{_ref}</li>)}</ul>;
+}"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |_| tr(),
+    hoist_to_enclosing_function,
+    r#"function App({ label }) {
+    const Item = () => <span>{label}</span>;
+    return <Item />;
+}"#,
+    r#"function App({ label }) {
+    var _ref = <span>{label}</span>;
+    const Item = () => _ref;
+    return <Item />;
+}"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |_| tr(),
+    declared_later,
+    r#"function App() {
+    return <Foo />;
+}
+const Foo = () => null;"#,
+    r#"function App() {
+    return <Foo />;
+}
+const Foo = () => null;"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |_| tr(),
+    reassigned,
+    r#"let text = "a";
+function App() {
+    return <div>{text}</div>;
+}
+text = "b";"#,
+    r#"let text = "a";
+function App() {
+    return <div>{text}</div>;
+}
+text = "b";"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |_| tr(),
+    ref_and_spread,
+    r#"const props = {};
+function App() {
+    return <div><a ref="link" /><b {...props} /><i {...getProps()} /></div>;
+}"#,
+    r#"const props = {};
+var _ref = <b {...props} />;
+function App() {
+    return <div><a ref="link" />{_ref}<i {...getProps()} /></div>;
+}"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |_| tr(),
+    called_before_declaration,
+    r#"import { render } from "react-dom";
+render(<App />);
+function App() {
+    return <div />;
+}"#,
+    r#"import { render } from "react-dom";
+var _ref = <div />;
+render(<App />);
+function App() {
+    return _ref;
+}"#
+);
@@ -1,7 +1,9 @@
 use crate::pass::Pass;
 use ast::*;
-use swc_atoms::js_word;
-use swc_common::{Fold, FoldWith, DUMMY_SP};
+use serde::{Deserialize, Serialize};
+use std::{path::Path, sync::Arc};
+use swc_atoms::{js_word, JsWord};
+use swc_common::{FileName, Fold, FoldWith, SourceMap, Span, Spanned, DUMMY_SP};
 
 #[cfg(test)]
 mod tests;
@@ -9,23 +11,76 @@ mod tests;
 /// `@babel/plugin-transform-react-display-name`
 ///
 /// Add displayName to React.createClass calls
-pub fn display_name() -> impl Pass {
-    DisplayName
+///
+/// The name of a class exported with `export default` is inferred from the
+/// file name. `index.js` uses the name of the parent directory instead.
+pub fn display_name(cm: Arc<SourceMap>, config: Config) -> impl Pass {
+    let callees = config
+        .callees
+        .iter()
+        .map(|callee| callee.split('.').map(JsWord::from).collect())
+        .collect();
+
+    DisplayName { cm, callees }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    /// Functions creating a component class, like `React.createClass`.
+    #[serde(default = "default_callees")]
+    pub callees: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            callees: default_callees(),
+        }
+    }
 }
 
-struct DisplayName;
+fn default_callees() -> Vec<String> {
+    vec!["React.createClass".into(), "createReactClass".into()]
+}
+
+struct DisplayName {
+    cm: Arc<SourceMap>,
+    /// Callees split by `.`.
+    callees: Vec<Vec<JsWord>>,
+}
+
+impl DisplayName {
+    fn folder(&self, name: Box<Expr>) -> Folder<'_> {
+        Folder {
+            callees: &self.callees,
+            name: Some(name),
+        }
+    }
+
+    fn file_name(&self, span: Span) -> Option<JsWord> {
+        let path = match self.cm.span_to_filename(span) {
+            FileName::Real(path) => path,
+            _ => return None,
+        };
+        let stem = path.file_stem()?.to_str()?;
+
+        if stem == "index" {
+            let dir = path.parent().and_then(Path::file_name)?;
+            return dir.to_str().map(JsWord::from);
+        }
+
+        Some(stem.into())
+    }
+}
 
 impl Fold<VarDeclarator> for DisplayName {
     fn fold(&mut self, decl: VarDeclarator) -> VarDeclarator {
         match decl.name {
             Pat::Ident(ref ident) => {
-                let init = decl.init.fold_with(&mut Folder {
-                    name: Some(box Expr::Lit(Lit::Str(Str {
-                        span: ident.span,
-                        value: ident.sym.clone(),
-                        has_escape: false,
-                    }))),
-                });
+                let init = decl
+                    .init
+                    .fold_with(&mut self.folder(str_lit(ident.span, ident.sym.clone())));
 
                 VarDeclarator { init, ..decl }
             }
@@ -39,15 +94,13 @@ impl Fold<ModuleDecl> for DisplayName {
         let decl = decl.fold_children(self);
 
         match decl {
-            ModuleDecl::ExportDefaultExpr(e) => {
-                ModuleDecl::ExportDefaultExpr(e.fold_with(&mut Folder {
-                    name: Some(box Expr::Lit(Lit::Str(Str {
-                        span: DUMMY_SP,
-                        value: "input".into(),
-                        has_escape: false,
-                    }))),
-                }))
-            }
+            ModuleDecl::ExportDefaultExpr(e) => match self.file_name(e.span()) {
+                Some(name) => {
+                    let mut folder = self.folder(str_lit(DUMMY_SP, name));
+                    ModuleDecl::ExportDefaultExpr(e.fold_with(&mut folder))
+                }
+                None => ModuleDecl::ExportDefaultExpr(e),
+            },
             _ => decl,
         }
     }
@@ -72,25 +125,17 @@ impl Fold<AssignExpr> for DisplayName {
                 computed: false,
                 ..
             })) => {
-                let right = expr.right.fold_with(&mut Folder {
-                    name: Some(box Expr::Lit(Lit::Str(Str {
-                        span: prop.span,
-                        value: prop.sym.clone(),
-                        has_escape: false,
-                    }))),
-                });
+                let right = expr
+                    .right
+                    .fold_with(&mut self.folder(str_lit(prop.span, prop.sym.clone())));
                 AssignExpr { right, ..expr }
             }
 
             PatOrExpr::Pat(box Pat::Ident(ref ident))
             | PatOrExpr::Expr(box Expr::Ident(ref ident)) => {
-                let right = expr.right.fold_with(&mut Folder {
-                    name: Some(box Expr::Lit(Lit::Str(Str {
-                        span: ident.span,
-                        value: ident.sym.clone(),
-                        has_escape: false,
-                    }))),
-                });
+                let right = expr
+                    .right
+                    .fold_with(&mut self.folder(str_lit(ident.span, ident.sym.clone())));
 
                 AssignExpr { right, ..expr }
             }
@@ -105,18 +150,13 @@ impl Fold<Prop> for DisplayName {
 
         match prop {
             Prop::KeyValue(KeyValueProp { key, value }) => {
-                let value = value.fold_with(&mut Folder {
-                    name: Some(match key {
-                        PropName::Ident(ref i) => box Expr::Lit(Lit::Str(Str {
-                            span: i.span,
-                            value: i.sym.clone(),
-                            has_escape: false,
-                        })),
-                        PropName::Str(ref s) => box Expr::Lit(Lit::Str(s.clone())),
-                        PropName::Num(n) => box Expr::Lit(Lit::Num(n)),
-                        PropName::Computed(ref c) => c.expr.clone(),
-                    }),
-                });
+                let name = match key {
+                    PropName::Ident(ref i) => str_lit(i.span, i.sym.clone()),
+                    PropName::Str(ref s) => box Expr::Lit(Lit::Str(s.clone())),
+                    PropName::Num(n) => box Expr::Lit(Lit::Num(n)),
+                    PropName::Computed(ref c) => c.expr.clone(),
+                };
+                let value = value.fold_with(&mut self.folder(name));
 
                 Prop::KeyValue(KeyValueProp { key, value })
             }
@@ -125,28 +165,37 @@ impl Fold<Prop> for DisplayName {
     }
 }
 
-struct Folder {
+fn str_lit(span: Span, value: JsWord) -> Box<Expr> {
+    box Expr::Lit(Lit::Str(Str {
+        span,
+        value,
+        has_escape: false,
+    }))
+}
+
+struct Folder<'a> {
+    callees: &'a [Vec<JsWord>],
     name: Option<Box<Expr>>,
 }
 
-impl Fold<ObjectLit> for Folder {
+impl Fold<ObjectLit> for Folder<'_> {
     /// Don't recurse into object.
     fn fold(&mut self, node: ObjectLit) -> ObjectLit {
         node
     }
 }
-impl Fold<ArrayLit> for Folder {
+impl Fold<ArrayLit> for Folder<'_> {
     /// Don't recurse into array.
     fn fold(&mut self, node: ArrayLit) -> ArrayLit {
         node
     }
 }
 
-impl Fold<CallExpr> for Folder {
+impl Fold<CallExpr> for Folder<'_> {
     fn fold(&mut self, expr: CallExpr) -> CallExpr {
         let expr = expr.fold_children(self);
 
-        if is_create_class_call(&expr) {
+        if is_create_class_call(self.callees, &expr) {
             let name = match self.name.take() {
                 Some(name) => name,
                 None => return expr,
@@ -158,26 +207,28 @@ impl Fold<CallExpr> for Folder {
     }
 }
 
-fn is_create_class_call(call: &CallExpr) -> bool {
+fn is_create_class_call(callees: &[Vec<JsWord>], call: &CallExpr) -> bool {
     match call.callee {
-        ExprOrSuper::Expr(box Expr::Member(MemberExpr {
-            obj:
-                ExprOrSuper::Expr(box Expr::Ident(Ident {
-                    sym: js_word!("React"),
-                    ..
-                })),
-            prop:
-                box Expr::Ident(Ident {
-                    sym: js_word!("createClass"),
-                    ..
-                }),
+        ExprOrSuper::Expr(ref callee) => callees.iter().any(|path| matches_path(callee, path)),
+        _ => false,
+    }
+}
+
+/// Returns true if `e` is `a.b.c` for `path` of `[a, b, c]`.
+fn matches_path(e: &Expr, path: &[JsWord]) -> bool {
+    let (last, rest) = match path.split_last() {
+        Some(v) => v,
+        None => return false,
+    };
+
+    match *e {
+        Expr::Ident(ref i) => rest.is_empty() && i.sym == *last,
+        Expr::Member(MemberExpr {
+            obj: ExprOrSuper::Expr(ref obj),
+            prop: box Expr::Ident(ref prop),
             computed: false,
             ..
-        }))
-        | ExprOrSuper::Expr(box Expr::Ident(Ident {
-            sym: js_word!("createReactClass"),
-            ..
-        })) => true,
+        }) => prop.sym == *last && matches_path(obj, rest),
         _ => false,
     }
 }
@@ -1,12 +1,14 @@
 use super::*;
 
-fn tr() -> impl Fold<Module> {
-    display_name()
+use crate::tests::Tester;
+
+fn tr(t: &mut Tester<'_>) -> impl Fold<Module> {
+    display_name(t.cm.clone(), Default::default())
 }
 
 test!(
     ::swc_ecma_parser::Syntax::default(),
-    |_| tr(),
+    |t| tr(t),
     assignment_expression,
     r#"
 foo = createReactClass({});
@@ -24,7 +26,7 @@ bar = React.createClass({
 
 test!(
     ::swc_ecma_parser::Syntax::default(),
-    |_| tr(),
+    |t| tr(t),
     nested,
     r#"
 var foo = qux(createReactClass({}));
@@ -42,7 +44,7 @@ var bar = qux(React.createClass({
 
 test!(
     ::swc_ecma_parser::Syntax::default(),
-    |_| tr(),
+    |t| tr(t),
     object_property,
     r#"
 ({
@@ -67,7 +69,7 @@ test!(
 
 test!(
     ::swc_ecma_parser::Syntax::default(),
-    |_| tr(),
+    |t| tr(t),
     variable_declarator,
     r#"
 var foo = createReactClass({});
@@ -82,3 +84,66 @@ var bar = React.createClass({
 });
 "#
 );
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |t| tr(t),
+    export_default,
+    r#"
+export default createReactClass({});
+"#,
+    r#"
+export default createReactClass({
+    displayName: "input"
+});
+"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |t| tr(t),
+    existing_display_name,
+    r#"
+var foo = createReactClass({
+    displayName: "Bar"
+});
+var bar = React.createClass({
+    "displayName": "Foo"
+});
+"#,
+    r#"
+var foo = createReactClass({
+    displayName: "Bar"
+});
+var bar = React.createClass({
+    "displayName": "Foo"
+});
+"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |t| display_name(
+        t.cm.clone(),
+        Config {
+            callees: vec!["preact.createClass".into(), "createClass".into()],
+        }
+    ),
+    custom_callees,
+    r#"
+var foo = preact.createClass({});
+var bar = createClass({});
+var baz = React.createClass({});
+var qux = preact["createClass"]({});
+"#,
+    r#"
+var foo = preact.createClass({
+    displayName: "foo"
+});
+var bar = createClass({
+    displayName: "bar"
+});
+var baz = React.createClass({});
+var qux = preact["createClass"]({});
+"#
+);
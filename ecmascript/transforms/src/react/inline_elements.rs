@@ -0,0 +1,226 @@
+use super::{
+    jsx::Options,
+    strip_dev_props::{ElementCall, ElementCallMatcher},
+};
+use crate::{pass::Pass, util::ExprFactory};
+use ast::*;
+use swc_atoms::JsWord;
+use swc_common::{Fold, FoldWith, DUMMY_SP};
+
+#[cfg(test)]
+mod tests;
+
+/// `@babel/plugin-transform-react-inline-elements`
+///
+/// Replaces calls created by `jsx` with the same `options` with the element
+/// objects react would create. This pass should run after `jsx` and before
+/// `hygiene`, and only for production builds.
+///
+/// Only elements of host components (like `div`) are inlined, as types of
+/// other components may have `defaultProps`. Calls with a spread, a `ref` or a
+/// key which is not a literal are left as is.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// React.createElement("div", { key: "a", id: "b" }, child);
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// ({
+///   $$typeof: _reactElementType(),
+///   type: "div",
+///   key: "a",
+///   ref: null,
+///   props: { id: "b", children: child },
+///   _owner: null
+/// });
+/// ```
+pub fn inline_elements(options: Options) -> impl Pass {
+    InlineElements {
+        matcher: ElementCallMatcher::new(&options),
+    }
+}
+
+struct InlineElements {
+    matcher: ElementCallMatcher,
+}
+
+impl Fold<Expr> for InlineElements {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+
+        match e {
+            Expr::Call(call) => match inline(&self.matcher, call) {
+                Ok(e) => e,
+                Err(call) => Expr::Call(call),
+            },
+            _ => e,
+        }
+    }
+}
+
+/// Returns `call` if it can't be inlined.
+fn inline(matcher: &ElementCallMatcher, call: CallExpr) -> Result<Expr, CallExpr> {
+    let kind = match matcher.kind(&call.callee) {
+        Some(kind @ ElementCall::Classic) | Some(kind @ ElementCall::Automatic) => kind,
+        _ => return Err(call),
+    };
+    if !can_inline(kind, &call.args) {
+        return Err(call);
+    }
+
+    let mut args = call.args.into_iter().map(|arg| arg.expr);
+    let ty = args.next().unwrap();
+
+    let mut props = vec![];
+    let mut key = None;
+    match args.next().map(|e| *e) {
+        None | Some(Expr::Lit(Lit::Null(..))) => {}
+        Some(Expr::Object(obj)) => {
+            for prop in obj.props {
+                match prop {
+                    PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp { key: k, value })) => {
+                        if prop_name(&k).map_or(false, |name| *name == *"key") {
+                            key = Some(key_str(&value).unwrap());
+                        } else {
+                            props.push(PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                                key: k,
+                                value,
+                            })));
+                        }
+                    }
+                    _ => unreachable!(),
+                }
+            }
+        }
+        Some(..) => unreachable!(),
+    }
+
+    if kind == ElementCall::Automatic {
+        // `_jsx(type, props, key)` has children in props.
+        key = args.next().map(|k| key_str(&k).unwrap());
+    } else {
+        let mut children = args.collect::<Vec<_>>();
+        let children = match children.len() {
+            0 => None,
+            1 => children.pop(),
+            _ => Some(box Expr::Array(ArrayLit {
+                span: DUMMY_SP,
+                elems: children.into_iter().map(|e| Some(e.as_arg())).collect(),
+            })),
+        };
+        if let Some(children) = children {
+            props.push(PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                key: PropName::Ident(quote_ident!("children")),
+                value: children,
+            })));
+        }
+    }
+
+    let null = || box Expr::Lit(Lit::Null(Null { span: DUMMY_SP }));
+    let prop = |name: &str, value: Box<Expr>| {
+        PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+            key: PropName::Ident(quote_ident!(name)),
+            value,
+        }))
+    };
+
+    Ok(Expr::Object(ObjectLit {
+        span: call.span,
+        props: vec![
+            prop(
+                "$$typeof",
+                box Expr::Call(CallExpr {
+                    span: DUMMY_SP,
+                    callee: helper!(react_element_type, "reactElementType"),
+                    args: vec![],
+                    type_args: Default::default(),
+                }),
+            ),
+            prop("type", ty),
+            prop(
+                "key",
+                key.map(|key| box Expr::Lit(Lit::Str(quote_str!(key))))
+                    .unwrap_or_else(null),
+            ),
+            prop("ref", null()),
+            prop(
+                "props",
+                box Expr::Object(ObjectLit {
+                    span: DUMMY_SP,
+                    props,
+                }),
+            ),
+            prop("_owner", null()),
+        ],
+    }))
+}
+
+/// Checks arguments of `React.createElement` or `_jsx`.
+fn can_inline(kind: ElementCall, args: &[ExprOrSpread]) -> bool {
+    if args.iter().any(|arg| arg.spread.is_some()) {
+        return false;
+    }
+
+    match args.first() {
+        Some(&ExprOrSpread {
+            expr: box Expr::Lit(Lit::Str(..)),
+            ..
+        }) => {}
+        _ => return false,
+    }
+
+    if kind == ElementCall::Automatic {
+        match args.get(2) {
+            Some(key) if key_str(&key.expr).is_none() => return false,
+            _ => {}
+        }
+    }
+
+    match args.get(1).map(|arg| &*arg.expr) {
+        None | Some(&Expr::Lit(Lit::Null(..))) => true,
+        Some(&Expr::Object(ref obj)) => obj.props.iter().all(|prop| match *prop {
+            PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                ref key,
+                ref value,
+            })) => match prop_name(key) {
+                // `_jsx` takes key as an argument.
+                Some(name) if *name == *"key" => {
+                    kind == ElementCall::Classic && key_str(value).is_some()
+                }
+                // `__self` and `__source` are only for development.
+                Some(name) => *name != *"ref" && *name != *"__self" && *name != *"__source",
+                None => false,
+            },
+            _ => false,
+        }),
+        // e.g. `_extends({}, props)`
+        Some(..) => false,
+    }
+}
+
+fn prop_name(key: &PropName) -> Option<&JsWord> {
+    match *key {
+        PropName::Ident(ref i) => Some(&i.sym),
+        PropName::Str(ref s) => Some(&s.value),
+        _ => None,
+    }
+}
+
+/// Converts a literal key to a string like react does.
+fn key_str(key: &Expr) -> Option<JsWord> {
+    match *key {
+        Expr::Lit(Lit::Str(ref s)) => Some(s.value.clone()),
+        Expr::Lit(Lit::Num(Number { value, .. }))
+            if value.fract() == 0.0 && value.abs() < 1e15 =>
+        {
+            Some(format!("{}", value as i64).into())
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,202 @@
+use super::*;
+use crate::react::{jsx, Options, Runtime};
+use swc_common::chain;
+
+fn syntax() -> ::swc_ecma_parser::Syntax {
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    })
+}
+
+test!(
+    syntax(),
+    |_| inline_elements(Default::default()),
+    basic,
+    r#"var a = React.createElement("div", {
+    key: "a",
+    id: "b"
+}, child);
+var b = React.createElement("span", null);"#,
+    r#"var a = {
+    $$typeof: _reactElementType(),
+    type: "div",
+    key: "a",
+    ref: null,
+    props: {
+        id: "b",
+        children: child
+    },
+    _owner: null
+};
+var b = {
+    $$typeof: _reactElementType(),
+    type: "span",
+    key: null,
+    ref: null,
+    props: {},
+    _owner: null
+};"#
+);
+
+test!(
+    syntax(),
+    |_| inline_elements(Default::default()),
+    children,
+    r#"var a = React.createElement("ul", {
+    key: 1
+}, React.createElement("li", null, "a"), b);"#,
+    r#"var a = {
+    $$typeof: _reactElementType(),
+    type: "ul",
+    key: "1",
+    ref: null,
+    props: {
+        children: [{
+            $$typeof: _reactElementType(),
+            type: "li",
+            key: null,
+            ref: null,
+            props: {
+                children: "a"
+            },
+            _owner: null
+        }, b]
+    },
+    _owner: null
+};"#
+);
+
+test!(
+    syntax(),
+    |_| inline_elements(Default::default()),
+    bail_out,
+    r#"var a = React.createElement(Foo, null);
+var b = React.createElement("div", {
+    ref: "input"
+});
+var c = React.createElement("div", {
+    key: id
+});
+var d = React.createElement("div", _extends({}, props));
+var e = React.createElement("div", {
+    ...props
+});
+var f = React.createElement("div", null, ...children);
+var g = React.createElement("div", {
+    __self: this
+});"#,
+    r#"var a = React.createElement(Foo, null);
+var b = React.createElement("div", {
+    ref: "input"
+});
+var c = React.createElement("div", {
+    key: id
+});
+var d = React.createElement("div", _extends({}, props));
+var e = React.createElement("div", {
+    ...props
+});
+var f = React.createElement("div", null, ...children);
+var g = React.createElement("div", {
+    __self: this
+});"#
+);
+
+test_exec!(
+    syntax(),
+    |_| chain!(jsx(Default::default()), inline_elements(Default::default())),
+    exec_same_as_create_element,
+    r#"
+// `createElement` of react in production mode.
+const React = {
+    createElement(type, config, ...children) {
+        const props = {};
+        let key = null;
+        if (config != null) {
+            if (config.key !== undefined) key = '' + config.key;
+            for (const name in config) {
+                if (name !== 'key' && name !== 'ref') props[name] = config[name];
+            }
+        }
+        if (children.length === 1) props.children = children[0];
+        else if (children.length > 1) props.children = children;
+        return {
+            $$typeof: Symbol.for('react.element'),
+            type,
+            key,
+            ref: null,
+            props,
+            _owner: null,
+        };
+    },
+};
+const createElement = React.createElement;
+const name = 'world';
+
+expect(<div />).toEqual(createElement('div', null));
+expect(<div key="a" id="b">hello</div>).toEqual(
+    createElement('div', { key: 'a', id: 'b' }, 'hello')
+);
+expect(<ul key={1}><li>a</li>{name}</ul>).toEqual(
+    createElement('ul', { key: 1 }, createElement('li', null, 'a'), name)
+);
+"#
+);
+
+test!(
+    syntax(),
+    |_| inline_elements(Options {
+        pragma: "h".into(),
+        ..Default::default()
+    }),
+    custom_pragma,
+    r#"var a = h("div", null);
+var b = React.createElement("div", null);"#,
+    r#"var a = {
+    $$typeof: _reactElementType(),
+    type: "div",
+    key: null,
+    ref: null,
+    props: {},
+    _owner: null
+};
+var b = React.createElement("div", null);"#
+);
+
+test!(
+    syntax(),
+    |_| {
+        let options = Options {
+            runtime: Runtime::Automatic,
+            ..Default::default()
+        };
+        chain!(jsx(options.clone()), inline_elements(options))
+    },
+    automatic,
+    r#"var a = <ul key={1}><li>a</li>{b}</ul>;
+var c = <Foo />;
+var d = <div key={id} />;"#,
+    r#"import { jsx as _jsx, jsxs as _jsxs } from "react/jsx-runtime";
+var a = {
+    $$typeof: _reactElementType(),
+    type: "ul",
+    key: "1",
+    ref: null,
+    props: {
+        children: [{
+            $$typeof: _reactElementType(),
+            type: "li",
+            key: null,
+            ref: null,
+            props: {
+                children: "a"
+            },
+            _owner: null
+        }, b]
+    },
+    _owner: null
+};
+var c = _jsx(Foo, {});
+var d = _jsx("div", {}, id);"#
+);
@@ -3,17 +3,19 @@ use crate::{
     util::{
         drop_span,
         options::{CM, SESSION},
-        ExprFactory, HANDLER,
+        prepend, undefined, ExprFactory, COMMENTS, HANDLER,
     },
 };
 use ast::*;
 use chashmap::CHashMap;
 use lazy_static::lazy_static;
-use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::{iter, mem, sync::Arc};
 use swc_atoms::{js_word, JsWord};
-use swc_common::{iter::IdentifyLast, FileName, Fold, FoldWith, Spanned, DUMMY_SP};
+use swc_common::{
+    comments::{Comment, CommentKind},
+    FileName, Fold, FoldWith, Span, Spanned, DUMMY_SP,
+};
 use swc_ecma_parser::{Parser, SourceFileInput, Syntax};
 
 #[cfg(test)]
@@ -35,6 +37,32 @@ pub struct Options {
 
     #[serde(default)]
     pub use_builtins: bool,
+
+    #[serde(default)]
+    pub runtime: Runtime,
+
+    /// Module to import the automatic runtime from.
+    #[serde(default = "default_import_source")]
+    pub import_source: String,
+
+    /// Annotate generated calls with `/*#__PURE__*/`.
+    #[serde(default = "default_pure")]
+    pub pure: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Runtime {
+    /// `React.createElement(type, props, ...children)`
+    Classic,
+    /// `_jsx(type, props, key)` with `_jsx` imported from `react/jsx-runtime`.
+    Automatic,
+}
+
+impl Default for Runtime {
+    fn default() -> Self {
+        Runtime::Classic
+    }
 }
 
 impl Default for Options {
@@ -45,6 +73,9 @@ impl Default for Options {
             throw_if_namespace: default_throw_if_namespace(),
             development: false,
             use_builtins: false,
+            runtime: Default::default(),
+            import_source: default_import_source(),
+            pure: default_pure(),
         }
     }
 }
@@ -61,7 +92,15 @@ fn default_throw_if_namespace() -> bool {
     true
 }
 
-fn parse_option(name: &str, src: String) -> Box<Expr> {
+fn default_import_source() -> String {
+    "react".into()
+}
+
+fn default_pure() -> bool {
+    true
+}
+
+pub(super) fn parse_option(name: &str, src: String) -> Box<Expr> {
     lazy_static! {
         static ref CACHE: CHashMap<Arc<String>, Box<Expr>> = CHashMap::with_capacity(2);
     }
@@ -97,6 +136,10 @@ fn parse_option(name: &str, src: String) -> Box<Expr> {
 /// `@babel/plugin-transform-react-jsx`
 ///
 /// Turn JSX into React function calls
+///
+/// `/** @jsx h */` and `/** @jsxFrag Fragment */` comments in a file override
+/// `pragma` and `pragmaFrag` if comments are available. `@jsxRuntime` and
+/// `@jsxImportSource` override `runtime` and `importSource` in the same way.
 pub fn jsx(options: Options) -> impl Pass {
     Jsx {
         pragma: ExprOrSuper::Expr(parse_option("pragma", options.pragma)),
@@ -106,6 +149,12 @@ pub fn jsx(options: Options) -> impl Pass {
         },
         use_builtins: options.use_builtins,
         throw_if_namespace: options.throw_if_namespace,
+        development: options.development,
+        runtime: options.runtime,
+        import_source: options.import_source.into(),
+        imports: vec![],
+        create_element: None,
+        pure: options.pure,
     }
 }
 
@@ -114,12 +163,61 @@ struct Jsx {
     pragma_frag: ExprOrSpread,
     use_builtins: bool,
     throw_if_namespace: bool,
+    development: bool,
+    runtime: Runtime,
+    import_source: JsWord,
+    /// `(imported, local)` of the automatic runtime used by current module.
+    imports: Vec<(JsWord, Ident)>,
+    /// Local name of `createElement` imported from `import_source`.
+    create_element: Option<Ident>,
+    pure: bool,
 }
 
 impl Jsx {
+    /// Returns the local name of `name` imported from the automatic runtime.
+    fn import(&mut self, name: &str) -> Ident {
+        if let Some(&(_, ref local)) = self.imports.iter().find(|(imported, _)| *imported == *name)
+        {
+            return local.clone();
+        }
+
+        let local = private_ident!(format!("_{}", name));
+        self.imports.push((name.into(), local.clone()));
+        local
+    }
+
+    /// Adds `/*#__PURE__*/` before a call created at `span`, so that bundlers
+    /// can drop unused elements.
+    fn add_pure_comment(&self, span: Span) {
+        if !self.pure || span.is_dummy() || !COMMENTS.is_set() {
+            return;
+        }
+
+        COMMENTS.with(|comments| {
+            let mut cmts = comments
+                .take_leading_comments(span.lo())
+                .unwrap_or_default();
+            if !cmts.iter().any(|cmt| cmt.text == "#__PURE__") {
+                cmts.push(Comment {
+                    kind: CommentKind::Block,
+                    span: DUMMY_SP,
+                    text: "#__PURE__".into(),
+                });
+            }
+            comments.add_leading(span.lo(), cmts);
+        });
+    }
+
     fn jsx_frag_to_expr(&mut self, el: JSXFragment) -> Expr {
         let span = el.span();
 
+        if self.runtime == Runtime::Automatic {
+            let name = box Expr::Ident(self.import("Fragment"));
+            return self.jsx_to_automatic_call(span, name, vec![], el.children);
+        }
+
+        self.add_pure_comment(span);
+
         Expr::Call(CallExpr {
             span,
             callee: self.pragma.clone(),
@@ -142,9 +240,26 @@ impl Jsx {
 
         let name = self.jsx_name(el.opening.name);
 
+        let callee = if self.runtime == Runtime::Automatic {
+            if !has_key_after_spread(&el.opening.attrs) {
+                return self.jsx_to_automatic_call(span, name, el.opening.attrs, el.children);
+            }
+
+            // `jsx()` takes key as an argument, so it can't override a key in
+            // the spread. `<div {...props} key="k" />` uses `createElement`.
+            self.create_element
+                .get_or_insert_with(|| private_ident!("_createElement"))
+                .clone()
+                .as_callee()
+        } else {
+            self.pragma.clone()
+        };
+
+        self.add_pure_comment(span);
+
         Expr::Call(CallExpr {
             span,
-            callee: self.pragma.clone(),
+            callee,
             args: iter::once(name.as_arg())
                 .chain(iter::once({
                     // Attributes
@@ -161,6 +276,116 @@ impl Jsx {
         })
     }
 
+    /// Creates `_jsx(type, props, key)`, `_jsxs(type, props, key)` or
+    /// `_jsxDEV(type, props, key, isStaticChildren, source, self)`.
+    fn jsx_to_automatic_call(
+        &mut self,
+        span: Span,
+        name: Box<Expr>,
+        attrs: Vec<JSXAttrOrSpread>,
+        children: Vec<JSXElementChild>,
+    ) -> Expr {
+        let mut key = None;
+        let mut source = None;
+        let mut this = None;
+        let mut props = Vec::with_capacity(attrs.len() + 1);
+
+        for attr in attrs {
+            let special = match attr {
+                JSXAttrOrSpread::JSXAttr(JSXAttr {
+                    name: JSXAttrName::Ident(ref i),
+                    ..
+                }) => match &*i.sym {
+                    "key" => Some("key"),
+                    "__source" if self.development => Some("__source"),
+                    "__self" if self.development => Some("__self"),
+                    _ => None,
+                },
+                _ => None,
+            };
+
+            match (special, attr) {
+                (Some("key"), JSXAttrOrSpread::JSXAttr(a)) => {
+                    key = Some(attr_value(a.value, a.span))
+                }
+                (Some("__source"), JSXAttrOrSpread::JSXAttr(a)) => {
+                    source = Some(attr_value(a.value, a.span))
+                }
+                (Some(_), JSXAttrOrSpread::JSXAttr(a)) => this = Some(attr_value(a.value, a.span)),
+                (_, attr) => props.push(attr),
+            }
+        }
+
+        let value = |v: Option<Box<Expr>>| v.unwrap_or_else(|| undefined(DUMMY_SP)).as_arg();
+
+        let mut children = children
+            .into_iter()
+            .filter_map(|c| self.jsx_elem_child_to_expr(c))
+            .collect::<Vec<_>>();
+        let is_static = children.len() > 1;
+        let children = match children.len() {
+            0 => None,
+            1 => Some(children.pop().unwrap().expr),
+            _ => Some(box Expr::Array(ArrayLit {
+                span: DUMMY_SP,
+                elems: children.into_iter().map(Some).collect(),
+            })),
+        };
+        if let Some(children) = children {
+            props.push(JSXAttrOrSpread::JSXAttr(JSXAttr {
+                span: DUMMY_SP,
+                name: JSXAttrName::Ident(quote_ident!("children")),
+                value: Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+                    span: DUMMY_SP,
+                    expr: JSXExpr::Expr(children),
+                })),
+            }));
+        }
+
+        let props = if props.is_empty() {
+            box Expr::Object(ObjectLit {
+                span: DUMMY_SP,
+                props: vec![],
+            })
+        } else {
+            self.fold_attrs(props)
+        };
+
+        let (callee, args) = if self.development {
+            (
+                self.import("jsxDEV"),
+                vec![
+                    name.as_arg(),
+                    props.as_arg(),
+                    value(key),
+                    Lit::Bool(Bool {
+                        span: DUMMY_SP,
+                        value: is_static,
+                    })
+                    .as_arg(),
+                    value(source),
+                    value(this),
+                ],
+            )
+        } else {
+            let callee = self.import(if is_static { "jsxs" } else { "jsx" });
+            let args = iter::once(name.as_arg())
+                .chain(iter::once(props.as_arg()))
+                .chain(key.map(|key| key.as_arg()))
+                .collect();
+            (callee, args)
+        };
+
+        self.add_pure_comment(span);
+
+        Expr::Call(CallExpr {
+            span,
+            callee: callee.as_callee(),
+            args,
+            type_args: Default::default(),
+        })
+    }
+
     fn jsx_elem_child_to_expr(&mut self, c: JSXElementChild) -> Option<ExprOrSpread> {
         Some(match c {
             JSXElementChild::JSXText(text) => {
@@ -192,11 +417,31 @@ impl Jsx {
         })
     }
 
-    fn fold_attrs(&mut self, attrs: Vec<JSXAttrOrSpread>) -> Box<Expr> {
+    fn fold_attrs(&mut self, mut attrs: Vec<JSXAttrOrSpread>) -> Box<Expr> {
         if attrs.is_empty() {
             return box Expr::Lit(Lit::Null(Null { span: DUMMY_SP }));
         }
 
+        for attr in &attrs {
+            if let JSXAttrOrSpread::JSXAttr(JSXAttr {
+                name: JSXAttrName::JSXNamespacedName(ref name),
+                ..
+            }) = *attr
+            {
+                self.check_namespace(name.span());
+            }
+        }
+
+        // `<div {...props} />` => `React.createElement('div', props)`
+        if attrs.len() == 1 {
+            if let JSXAttrOrSpread::SpreadElement(..) = attrs[0] {
+                match attrs.pop() {
+                    Some(JSXAttrOrSpread::SpreadElement(e)) => return e.expr,
+                    _ => unreachable!(),
+                }
+            }
+        }
+
         let is_complex = attrs.iter().any(|a| match *a {
             JSXAttrOrSpread::SpreadElement(..) => true,
             _ => false,
@@ -262,6 +507,161 @@ impl Jsx {
     }
 }
 
+impl Jsx {
+    /// Applies `@jsx` and `@jsxFrag` pragmas in a comment.
+    fn parse_pragma_comment(&mut self, text: &str) {
+        let mut words = text.split_whitespace();
+
+        while let Some(word) = words.next() {
+            match word {
+                "@jsx" => {
+                    if let Some(pragma) = words.next() {
+                        self.pragma = ExprOrSuper::Expr(parse_option("pragma", pragma.into()));
+                    }
+                }
+                "@jsxFrag" => {
+                    if let Some(pragma_frag) = words.next() {
+                        self.pragma_frag = ExprOrSpread {
+                            spread: None,
+                            expr: parse_option("pragmaFrag", pragma_frag.into()),
+                        };
+                    }
+                }
+                "@jsxRuntime" => match words.next() {
+                    Some("classic") => self.runtime = Runtime::Classic,
+                    Some("automatic") => self.runtime = Runtime::Automatic,
+                    _ => {}
+                },
+                "@jsxImportSource" => {
+                    if let Some(import_source) = words.next() {
+                        self.import_source = import_source.into();
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl Fold<Module> for Jsx {
+    fn fold(&mut self, module: Module) -> Module {
+        let pragma = self.pragma.clone();
+        let pragma_frag = self.pragma_frag.clone();
+        let runtime = self.runtime;
+        let import_source = self.import_source.clone();
+
+        if COMMENTS.is_set() {
+            COMMENTS.with(|comments| {
+                for item in &module.body {
+                    if let Some(cmts) = comments.leading_comments(item.span().lo()) {
+                        for cmt in cmts.iter() {
+                            self.parse_pragma_comment(&cmt.text);
+                        }
+                    }
+                }
+            });
+        }
+
+        let mut module = module.fold_children(self);
+
+        if !self.imports.is_empty() {
+            let src = if self.development {
+                format!("{}/jsx-dev-runtime", self.import_source)
+            } else {
+                format!("{}/jsx-runtime", self.import_source)
+            };
+
+            prepend(
+                &mut module.body,
+                ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                    span: DUMMY_SP,
+                    specifiers: self
+                        .imports
+                        .drain(..)
+                        .map(|(imported, local)| {
+                            ImportSpecifier::Specific(ImportSpecific {
+                                span: DUMMY_SP,
+                                local,
+                                imported: Some(quote_ident!(imported)),
+                            })
+                        })
+                        .collect(),
+                    src: quote_str!(src),
+                })),
+            );
+        }
+
+        if let Some(local) = self.create_element.take() {
+            prepend(
+                &mut module.body,
+                ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                    span: DUMMY_SP,
+                    specifiers: vec![ImportSpecifier::Specific(ImportSpecific {
+                        span: DUMMY_SP,
+                        local,
+                        imported: Some(quote_ident!("createElement")),
+                    })],
+                    src: quote_str!(self.import_source.clone()),
+                })),
+            );
+        }
+
+        // Pragma comments only apply to the file containing them.
+        self.pragma = pragma;
+        self.pragma_frag = pragma_frag;
+        self.runtime = runtime;
+        self.import_source = import_source;
+
+        module
+    }
+}
+
+impl Fold<Script> for Jsx {
+    fn fold(&mut self, script: Script) -> Script {
+        let pragma = self.pragma.clone();
+        let pragma_frag = self.pragma_frag.clone();
+        let runtime = self.runtime;
+        let import_source = self.import_source.clone();
+
+        if COMMENTS.is_set() {
+            COMMENTS.with(|comments| {
+                for stmt in &script.body {
+                    if let Some(cmts) = comments.leading_comments(stmt.span().lo()) {
+                        for cmt in cmts.iter() {
+                            self.parse_pragma_comment(&cmt.text);
+                        }
+                    }
+                }
+            });
+        }
+
+        let script = script.fold_children(self);
+
+        // A script cannot import the runtime.
+        if !self.imports.is_empty() || self.create_element.is_some() {
+            self.imports.clear();
+            self.create_element = None;
+
+            HANDLER.with(|handler| {
+                handler
+                    .struct_span_err(
+                        script.span,
+                        "The automatic JSX runtime requires imports, which are not allowed in a \
+                         script. Use the classic runtime or parse the file as a module",
+                    )
+                    .emit()
+            });
+        }
+
+        self.pragma = pragma;
+        self.pragma_frag = pragma_frag;
+        self.runtime = runtime;
+        self.import_source = import_source;
+
+        script
+    }
+}
+
 impl Fold<Expr> for Jsx {
     fn fold(&mut self, expr: Expr) -> Expr {
         let expr = expr.fold_children(self);
@@ -288,7 +688,44 @@ impl Fold<Expr> for Jsx {
     }
 }
 
+impl Fold<JSXAttrValue> for Jsx {
+    fn fold(&mut self, value: JSXAttrValue) -> JSXAttrValue {
+        let value = value.fold_children(self);
+
+        // Parser wraps elements and fragments with an expression container,
+        // but they can be attribute values in an ast created by other passes.
+        let expr = match value {
+            JSXAttrValue::JSXElement(el) => self.jsx_elem_to_expr(*el),
+            JSXAttrValue::JSXFragment(frag) => self.jsx_frag_to_expr(frag),
+            _ => return value,
+        };
+
+        JSXAttrValue::JSXExprContainer(JSXExprContainer {
+            span: expr.span(),
+            expr: JSXExpr::Expr(box expr),
+        })
+    }
+}
+
 impl Jsx {
+    /// Reports a namespaced name at `span` if `throwIfNamespace` is enabled.
+    fn check_namespace(&self, span: Span) {
+        if !self.throw_if_namespace {
+            return;
+        }
+
+        HANDLER.with(|handler| {
+            handler
+                .struct_span_err(
+                    span,
+                    "JSX Namespace is disabled by default because react does not support it \
+                     yet. You can specify jsc.transform.react.throwIfNamespace to false to \
+                     override default behavior",
+                )
+                .emit()
+        });
+    }
+
     fn jsx_name(&self, name: JSXElementName) -> Box<Expr> {
         let span = name.span();
         match name {
@@ -311,19 +748,7 @@ impl Jsx {
                 }
             }
             JSXElementName::JSXNamespacedName(JSXNamespacedName { ref ns, ref name }) => {
-                if self.throw_if_namespace {
-                    HANDLER.with(|handler| {
-                        handler
-                            .struct_span_err(
-                                span,
-                                "JSX Namespace is disabled by default because react does not \
-                                 support it yet. You can specify \
-                                 jsc.transform.react.throwIfNamespace to false to override \
-                                 default behavior",
-                            )
-                            .emit()
-                    });
-                }
+                self.check_namespace(span);
                 box Expr::Lit(Lit::Str(Str {
                     span,
                     value: format!("{}:{}", ns.sym, name.sym).into(),
@@ -361,24 +786,41 @@ impl Jsx {
     }
 }
 
+/// Returns true if `key` is specified after a spread.
+fn has_key_after_spread(attrs: &[JSXAttrOrSpread]) -> bool {
+    attrs
+        .iter()
+        .skip_while(|attr| match **attr {
+            JSXAttrOrSpread::SpreadElement(..) => false,
+            _ => true,
+        })
+        .any(|attr| match *attr {
+            JSXAttrOrSpread::JSXAttr(JSXAttr {
+                name: JSXAttrName::Ident(ref i),
+                ..
+            }) => i.sym == *"key",
+            _ => false,
+        })
+}
+
 fn attr_to_prop(a: JSXAttr) -> Prop {
     let key = to_prop_name(a.name);
-    let value = a
-        .value
+    let value = attr_value(a.value, key.span());
+    Prop::KeyValue(KeyValueProp { key, value })
+}
+
+/// `span` is used for `true` if `value` is `None`.
+fn attr_value(value: Option<JSXAttrValue>, span: Span) -> Box<Expr> {
+    value
         .map(|v| match v {
+            JSXAttrValue::Lit(lit) => box Expr::Lit(lit),
             JSXAttrValue::JSXExprContainer(JSXExprContainer {
                 expr: JSXExpr::Expr(e),
                 ..
             }) => e,
             _ => unreachable!(),
         })
-        .unwrap_or_else(|| {
-            box Expr::Lit(Lit::Bool(Bool {
-                span: key.span(),
-                value: true,
-            }))
-        });
-    Prop::KeyValue(KeyValueProp { key, value })
+        .unwrap_or_else(|| box Expr::Lit(Lit::Bool(Bool { span, value: true })))
 }
 
 fn to_prop_name(n: JSXAttrName) -> PropName {
@@ -404,36 +846,47 @@ fn to_prop_name(n: JSXAttrName) -> PropName {
     }
 }
 
+/// Normalizes whitespaces of a jsx text like react does.
+///
+///  - Lines containing only whitespaces are removed.
+///  - Whitespaces touching a line break are removed.
+///  - Other lines are joined with a single space.
+///
+/// Only spaces and tabs are treated as whitespace, so entities like `&nbsp;`
+/// (which are decoded by the lexer) are preserved.
 fn jsx_text_to_str(t: JsWord) -> JsWord {
-    lazy_static! {
-        static ref SPACE_NL_START: Regex = { Regex::new("^\\s*\n\\s*").unwrap() };
-        static ref SPACE_NL_END: Regex = { Regex::new("\\s*\n\\s*$").unwrap() };
-    };
-
-    if t == *" " {
-        return t;
-    }
-    if !t.contains(' ') && !t.contains('\n') {
+    if !t.contains(|c| c == '\n' || c == '\r' || c == '\t') {
         return t;
     }
 
-    let s = SPACE_NL_START.replace_all(&t, "");
-    let s = SPACE_NL_END.replace_all(&s, "");
-    let need_leading_space = s.starts_with(' ');
-    let need_trailing_space = s.ends_with(' ');
-
-    let mut buf = String::from(if need_leading_space { " " } else { "" });
+    let lines = t
+        .split("\r\n")
+        .flat_map(|s| s.split(|c| c == '\n' || c == '\r'));
+    let lines = lines.collect::<Vec<_>>();
+    let last_non_empty = lines
+        .iter()
+        .rposition(|line| line.contains(|c| c != ' ' && c != '\t'))
+        .unwrap_or(0);
+
+    let mut buf = String::new();
+    for (i, line) in lines.iter().enumerate() {
+        let line = line.replace('\t', " ");
+        let mut line = &*line;
+        if i != 0 {
+            line = line.trim_start_matches(' ');
+        }
+        if i != lines.len() - 1 {
+            line = line.trim_end_matches(' ');
+        }
+        if line.is_empty() {
+            continue;
+        }
 
-    for (last, s) in s.split_ascii_whitespace().identify_last() {
-        buf.push_str(s);
-        if !last {
+        buf.push_str(line);
+        if i != last_non_empty {
             buf.push(' ');
         }
     }
 
-    if need_trailing_space && !buf.ends_with(' ') {
-        buf.push(' ');
-    }
-
     buf.into()
 }
@@ -6,11 +6,17 @@ use crate::{
     },
     modules::common_js::common_js,
     react::display_name,
+    tests::{AsScript, Tester},
 };
 use swc_common::chain;
 
-fn tr(options: Options) -> impl Fold<Module> {
-    chain!(jsx(options), display_name(), Classes::default(), arrow(),)
+fn tr(t: &mut Tester<'_>, options: Options) -> impl Fold<Module> {
+    chain!(
+        jsx(options),
+        display_name(t.cm.clone(), Default::default()),
+        Classes::default(),
+        arrow(),
+    )
 }
 
 test!(
@@ -18,7 +24,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_add_appropriate_newlines,
     r#"
 <Component
@@ -37,7 +43,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_arrow_functions,
     r#"
 var foo = function () {
@@ -67,7 +73,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_concatenates_adjacent_string_literals,
     r#"
 var x =
@@ -104,7 +110,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_display_name_assignment_expression,
     r#"var Component;
 Component = React.createClass({
@@ -127,7 +133,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_display_name_export_default,
     r#"
 export default React.createClass({
@@ -151,7 +157,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_display_name_if_missing,
     r#"
 var Whateva = React.createClass({
@@ -189,7 +195,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_display_name_object_declaration,
     r#"
 exports = {
@@ -215,7 +221,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_display_name_property_assignment,
     r#"
 exports.Component = React.createClass({
@@ -239,7 +245,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_display_name_variable_declaration,
     r#"
 var Component = React.createClass({
@@ -263,7 +269,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_dont_coerce_expression_containers,
     r#"
 <Text>
@@ -282,12 +288,14 @@ test!(
 );
 
 test!(
-    ignore,
     ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Options {
+        pragma: "foo.bar".into(),
+        ..Default::default()
+    }),
     react_honor_custom_jsx_comment_if_jsx_pragma_option_set,
     r#"/** @jsx dom */
 
@@ -307,12 +315,11 @@ var profile = dom("div", null, dom("img", {
 );
 
 test!(
-    ignore,
     ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_honor_custom_jsx_comment,
     r#"
 /** @jsx dom */
@@ -339,7 +346,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Options {
+    |t| tr(t, Options {
         pragma: "dom".into(),
         ..Default::default()
     }),
@@ -365,7 +372,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_jsx_with_retainlines_option,
     r#"var div = <div>test</div>;"#,
     r#"var div = React.createElement("div", null, "test");"#
@@ -376,7 +383,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_jsx_without_retainlines_option,
     r#"var div = <div>test</div>;"#,
     r#"var div = React.createElement("div", null, "test");"#
@@ -389,7 +396,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_optimisation_react_constant_elements,
     r#"
 class App extends React.Component {
@@ -453,7 +460,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| chain!(tr(Default::default()), PropertyLiteral),
+    |t| chain!(tr(t, Default::default()), PropertyLiteral::default()),
     react_should_add_quotes_es3,
     r#"var es3 = <F aaa new const var default foo-bar/>;"#,
     r#"
@@ -473,7 +480,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_should_allow_constructor_as_prop,
     r#"<Component constructor="foo" />;"#,
     r#"
@@ -488,7 +495,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_should_allow_deeper_js_namespacing,
     r#"<Namespace.DeepNamespace.Component />;"#,
     r#"React.createElement(Namespace.DeepNamespace.Component, null);"#
@@ -499,7 +506,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_should_allow_elements_as_attributes,
     r#"<div attr=<div /> />"#,
     r#"
@@ -513,7 +520,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_should_allow_js_namespacing,
     r#"<Namespace.Component />;"#,
     r#"React.createElement(Namespace.Component, null);"#
@@ -524,7 +531,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_should_allow_nested_fragments,
     r#"
 <div>
@@ -555,12 +562,11 @@ React.createElement("div", null, React.createElement(
 );
 
 test!(
-    ignore,
     ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_should_allow_no_pragmafrag_if_frag_unused,
     r#"
 /** @jsx dom */
@@ -574,12 +580,11 @@ dom("div", null, "no fragment is used");
 );
 
 test!(
-    ignore,
     ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_should_allow_pragmafrag_and_frag,
     r#"
 /** @jsx dom */
@@ -600,7 +605,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_should_avoid_wrapping_in_extra_parens_if_not_needed,
     r#"
 var x = <div>
@@ -632,7 +637,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_should_convert_simple_tags,
     r#"var x = <div></div>;"#,
     r#"var x = React.createElement("div", null);"#
@@ -643,7 +648,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_should_convert_simple_text,
     r#"var x = <div>text</div>;"#,
     r#"var x = React.createElement("div", null, "text");"#
@@ -654,7 +659,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_should_escape_xhtml_jsxattribute,
     r#"
 <div id="wôw" />;
@@ -680,7 +685,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_should_escape_xhtml_jsxtext_1,
     r#"
 <div>wow</div>;
@@ -711,7 +716,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_should_escape_xhtml_jsxtext_2,
     r#"
 <div>this should not parse as unicode: \u00a0</div>;
@@ -729,7 +734,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_should_escape_xhtml_jsxtext_3,
     r#"
 <div>this should parse as nbsp:   </div>;
@@ -744,7 +749,19 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
+    text_literal_nbsp,
+    "<div>this should parse as nbsp: \u{a0} </div>;",
+    r#"React.createElement("div", null, "this should parse as nbsp: \xA0 ");"#,
+    ok_if_code_eq
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |t| tr(t, Default::default()),
     react_should_handle_attributed_elements,
     r#"
 var HelloMessage = React.createClass({
@@ -780,7 +797,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_should_handle_has_own_property_correctly,
     r#"<hasOwnProperty>testing</hasOwnProperty>;"#,
     r#"React.createElement("hasOwnProperty", null, "testing");"#
@@ -791,7 +808,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_should_have_correct_comma_in_nested_children,
     r#"
 var x = <div>
@@ -815,7 +832,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_should_insert_commas_after_expressions_before_whitespace,
     r#"
 var x =
@@ -850,7 +867,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_should_not_add_quotes_to_identifier_names,
     r#"var e = <F aaa new const var default foo-bar/>;"#,
     r#"
@@ -870,7 +887,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_should_not_mangle_expressioncontainer_attribute_values,
     r#"<button data-value={"a value\n  with\nnewlines\n   and spaces"}>Button</button>;"#,
     r#"
@@ -885,7 +902,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_should_not_strip_nbsp_even_coupled_with_other_whitespace,
     r#"<div>&nbsp; </div>;"#,
     r#"React.createElement("div", null, "\xA0 ");"#,
@@ -897,7 +914,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_should_not_strip_tags_with_a_single_child_of_nbsp,
     r#"<div>&nbsp;</div>;"#,
     r#"React.createElement("div", null, "\xA0");"#,
@@ -911,7 +928,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_should_properly_handle_comments_between_props,
     r#"
 var x = (
@@ -942,7 +959,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_should_quote_jsx_attributes,
     r#"<button data-value='a value'>Button</button>;"#,
     r#"
@@ -957,7 +974,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Options {
+    |t| tr(t, Options {
         pragma: "h".into(),
         throw_if_namespace: false,
         ..Default::default()
@@ -974,7 +991,35 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Options {
+        throw_if_namespace: false,
+        ..Default::default()
+    }),
+    namespaced_attr_value_if_flag,
+    r#"<svg:circle />;
+<image xlink:href="a.png" />;"#,
+    r#"React.createElement("svg:circle", null);
+React.createElement("image", {
+  "xlink:href": "a.png"
+});"#
+);
+
+#[test]
+fn throw_if_namespace_element() {
+    assert!(reports_error(Default::default(), r#"<svg:circle />;"#, false));
+}
+
+#[test]
+fn throw_if_namespace_attr() {
+    assert!(reports_error(Default::default(), r#"<image xlink:href="a.png" />;"#, false));
+}
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |t| tr(t, Default::default()),
     react_should_transform_known_hyphenated_tags,
     r#"<font-face />;"#,
     r#"React.createElement("font-face", null);"#
@@ -985,7 +1030,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_wraps_props_in_react_spread_for_first_spread_attributes,
     r#"
 <Component { ... x } y
@@ -1004,7 +1049,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_wraps_props_in_react_spread_for_last_spread_attributes,
     r#"<Component y={2} z { ... x } />"#,
     r#"
@@ -1020,7 +1065,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Default::default()),
+    |t| tr(t, Default::default()),
     react_wraps_props_in_react_spread_for_middle_spread_attributes,
     r#"<Component y={2} { ... x } z />"#,
     r#"
@@ -1036,7 +1081,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Options {
+    |t| tr(t, Options {
         use_builtins: true,
         ..Default::default()
     },),
@@ -1054,7 +1099,75 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Options {
+    |t| tr(t, Options {
+        use_builtins: true,
+        ..Default::default()
+    }),
+    use_builtins_trailing_spread,
+    r#"<Component y={2} z {...x} />"#,
+    r#"
+React.createElement(Component, Object.assign({
+  y: 2,
+  z: true
+}, x));
+"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |t| tr(t, Options {
+        use_builtins: true,
+        ..Default::default()
+    }),
+    use_builtins_interleaved,
+    r#"<Component a {...x} b="c" {...y} {...z} d />"#,
+    r#"
+React.createElement(Component, Object.assign({
+  a: true
+}, x, {
+  b: "c"
+}, y, z, {
+  d: true
+}));
+"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |t| tr(t, Default::default()),
+    spread_only,
+    r#"<Component {...props} />;
+<div {...props}>text</div>;"#,
+    r#"React.createElement(Component, props);
+React.createElement("div", props, "text");"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |t| tr(t, Options {
+        use_builtins: true,
+        ..Default::default()
+    }),
+    use_builtins_spread_only,
+    r#"<Component {...props} />"#,
+    r#"React.createElement(Component, props);"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |t| tr(t, Options {
         use_builtins: true,
         ..Default::default()
     },),
@@ -1070,8 +1183,8 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| chain!(
-        tr(Options {
+    |t| chain!(
+        tr(t, Options {
             use_builtins: true,
             ..Default::default()
         }),
@@ -1091,7 +1204,7 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Options {
+    |t| tr(t, Options {
         use_builtins: true,
         ..Default::default()
     }),
@@ -1106,8 +1219,8 @@ test!(
         jsx: true,
         ..Default::default()
     }),
-    |_| chain!(
-        tr(Options {
+    |t| chain!(
+        tr(t, Options {
             use_builtins: true,
             ..Default::default()
         }),
@@ -1123,20 +1236,137 @@ _react.default.createElement('div', {
 }, 'Hello World');"
 );
 
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |t| tr(t, Default::default()),
+    text_multiline,
+    "<div>
+    first line
+        second   line\t
+\t
+    <b>  bold  </b> and{' '}
+    {name}   tail
+</div>;",
+    r#"React.createElement("div", null, "first line second   line", React.createElement("b", null, "  bold  "), " and", ' ', name, "   tail");"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |t| tr(t, Default::default()),
+    text_whitespace_only_children,
+    "<div>
+    <span />
+
+    <span />
+</div>;",
+    r#"React.createElement("div", null, React.createElement("span", null), React.createElement("span", null));"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |t| tr(t, Default::default()),
+    text_entities,
+    "<p>
+    Tom &amp; Jerry&nbsp;&copy;
+    &#65;&#x42; &lt;tag&gt; &unknown; &
+</p>;",
+    r#"React.createElement("p", null, "Tom & Jerry\xA0\xA9 AB <tag> &unknown; &");"#,
+    ok_if_code_eq
+);
+
 #[test]
 fn jsx_text() {
     assert_eq!(jsx_text_to_str(" ".into()), *" ");
     assert_eq!(jsx_text_to_str("Hello world".into()), *"Hello world");
-    //    assert_eq!(jsx_text_to_str(" \n".into()), *" ");
+    assert_eq!(jsx_text_to_str(" \n".into()), *"");
+    assert_eq!(
+        jsx_text_to_str("\n  Hello\n  world\n".into()),
+        *"Hello world"
+    );
+    assert_eq!(jsx_text_to_str("\r\n\tHello\r\n".into()), *"Hello");
+    assert_eq!(jsx_text_to_str("Hello \n".into()), *"Hello");
+    assert_eq!(jsx_text_to_str(" Hello\n".into()), *" Hello");
+    assert_eq!(
+        jsx_text_to_str("\u{a0}\n  Hello\u{a0}\n".into()),
+        *"\u{a0} Hello\u{a0}"
+    );
 }
 
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |t| tr(t, Default::default()),
+    react_multiline_text_with_expressions,
+    r#"
+<div>
+  Hello, {name}!
+  You have {count}
+  new messages.
+
+  {" "}
+</div>;
+"#,
+    r#"
+React.createElement("div", null, "Hello, ", name, "! You have ", count, "new messages.", " ");
+"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |t| tr(t, Default::default()),
+    react_should_keep_entities_touching_line_breaks,
+    r#"
+<div>
+  &nbsp;a &amp; b&#33;&nbsp;
+  c&#x3e;
+</div>;
+"#,
+    r#"
+React.createElement("div", null, "\xA0a & b!\xA0 c>");
+"#,
+    ok_if_code_eq
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |t| tr(t, Default::default()),
+    react_fragment_text_children,
+    r#"
+<>
+  first
+  <b>  second  </b>
+  {third}   fourth
+</>;
+"#,
+    r#"
+React.createElement(React.Fragment, null, "first", React.createElement("b", null, "  second  "), third, "   fourth");
+"#
+);
+
 // https://github.com/swc-project/swc/issues/542
 test!(
     ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
         jsx: true,
         ..Default::default()
     }),
-    |_| tr(Options {
+    |t| tr(t, Options {
         use_builtins: true,
         ..Default::default()
     }),
@@ -1145,3 +1375,421 @@ test!(
     "let page = React.createElement('p', null, 'Click ', React.createElement('em', null, 'New \
      melody'), ' listen to a randomly generated melody');"
 );
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |t| tr(t, Options {
+        pragma: "h".into(),
+        pragma_frag: "Fragment".into(),
+        ..Default::default()
+    }),
+    custom_pragma_and_pragma_frag,
+    r#"<div />;
+<>
+  <div />
+  <span>text</span>
+</>;"#,
+    r#"h("div", null);
+h(Fragment, null, h("div", null), h("span", null, "text"));"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |t| tr(t, Options {
+        pragma: "preact.h".into(),
+        pragma_frag: "preact.Fragment".into(),
+        ..Default::default()
+    }),
+    dotted_pragma,
+    r#"<><div /></>;"#,
+    r#"preact.h(preact.Fragment, null, preact.h("div", null));"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |t| tr(t, Options {
+        pragma: "h".into(),
+        pragma_frag: "Fragment".into(),
+        ..Default::default()
+    }),
+    comment_pragma_beats_option,
+    r#"/** @jsx dom */
+/** @jsxFrag DomFrag */
+<><div /></>;"#,
+    r#"dom(DomFrag, null, dom("div", null));"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |t| tr(t, Default::default()),
+    empty_fragment,
+    r#"var x = <></>;
+var y = <>
+
+</>;"#,
+    r#"var x = React.createElement(React.Fragment, null);
+var y = React.createElement(React.Fragment, null);"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |t| tr(t, Default::default()),
+    fragment_mixed_children,
+    r#"<>
+  text {value} <b key="b" /> more
+  {list.map(i => <i key={i} />)}
+</>;"#,
+    r#"React.createElement(React.Fragment, null, "text ", value, " ", React.createElement("b", {
+    key: "b"
+}), " more", list.map(function(i) {
+    return React.createElement("i", {
+        key: i
+    });
+}));"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |t| tr(t, Default::default()),
+    fragment_as_attr_value,
+    r#"<Layout header=<><h1 /></> footer={<>footer</>} />;"#,
+    r#"React.createElement(Layout, {
+    header: React.createElement(React.Fragment, null, React.createElement("h1", null)),
+    footer: React.createElement(React.Fragment, null, "footer")
+});"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |t| chain!(crate::react::jsx_self(true, Default::default()), tr(t, Default::default())),
+    fragment_without_self,
+    r#"<><div /></>;"#,
+    r#"React.createElement(React.Fragment, null, React.createElement("div", null));"#
+);
+
+fn automatic() -> Options {
+    Options {
+        runtime: Runtime::Automatic,
+        ..Default::default()
+    }
+}
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |t| tr(t, automatic()),
+    automatic_single_child,
+    r#"<div className="a">text</div>;
+<div {...props} a="b" />;"#,
+    r#"import { jsx as _jsx } from "react/jsx-runtime";
+_jsx("div", {
+    className: "a",
+    children: "text"
+});
+_jsx("div", _extends({}, props, {
+    a: "b"
+}));"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |t| tr(t, automatic()),
+    automatic_multiple_children,
+    r#"<>
+  <div />
+  <span>{a}{b}</span>
+</>;"#,
+    r#"import { Fragment as _Fragment, jsx as _jsx, jsxs as _jsxs } from "react/jsx-runtime";
+_jsxs(_Fragment, {
+    children: [_jsx("div", {}), _jsxs("span", {
+        children: [a, b]
+    })]
+});"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |t| tr(t, automatic()),
+    automatic_keyed,
+    r#"<ul>{items.map(i => <li key={i.id}>{i.name}</li>)}</ul>;"#,
+    r#"import { jsx as _jsx } from "react/jsx-runtime";
+_jsx("ul", {
+    children: items.map(function(i) {
+        return _jsx("li", {
+            children: i.name
+        }, i.id);
+    })
+});"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |t| tr(t, Default::default()),
+    key_before_and_after_spread,
+    r#"<div key="k" {...props} />;
+<div {...props} key="k" />;"#,
+    r#"React.createElement("div", _extends({
+    key: "k"
+}, props));
+React.createElement("div", _extends({}, props, {
+    key: "k"
+}));"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |t| tr(t, automatic()),
+    automatic_key_before_spread,
+    r#"<div key="k" {...props}>a</div>;"#,
+    r#"import { jsx as _jsx } from "react/jsx-runtime";
+_jsx("div", _extends({}, props, {
+    children: "a"
+}), "k");"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |t| tr(t, automatic()),
+    automatic_key_after_spread,
+    r#"<div {...props} key="k">a</div>;
+<span key="k" />;"#,
+    r#"import { createElement as _createElement } from "react";
+import { jsx as _jsx } from "react/jsx-runtime";
+_createElement("div", _extends({}, props, {
+    key: "k"
+}), "a");
+_jsx("span", {}, "k");"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |t| chain!(
+        crate::react::jsx_src(true, t.cm.clone(), Default::default()),
+        crate::react::jsx_self(true, Default::default()),
+        jsx(Options {
+            development: true,
+            ..automatic()
+        })
+    ),
+    automatic_dev,
+    r#"<div key="a">text</div>;
+<><b /><i /></>;"#,
+    r#"import { jsxDEV as _jsxDEV, Fragment as _Fragment } from "react/jsx-dev-runtime";
+var _jsxFileName = "input.js";
+_jsxDEV("div", {
+    children: "text"
+}, "a", false, {
+    fileName: _jsxFileName,
+    lineNumber: 1,
+    columnNumber: 1
+}, void 0);
+_jsxDEV(_Fragment, {
+    children: [_jsxDEV("b", {}, void 0, false, {
+        fileName: _jsxFileName,
+        lineNumber: 2,
+        columnNumber: 3
+    }, void 0), _jsxDEV("i", {}, void 0, false, {
+        fileName: _jsxFileName,
+        lineNumber: 2,
+        columnNumber: 8
+    }, void 0)]
+}, void 0, true, void 0, void 0);"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |t| tr(t, Default::default()),
+    automatic_comment_pragmas,
+    r#"/** @jsxRuntime automatic */
+/** @jsxImportSource preact */
+<div />;"#,
+    r#"import { jsx as _jsx } from "preact/jsx-runtime";
+_jsx("div", {});"#
+);
+
+/// Transforms `src` and prints it with comments.
+fn print_with_comments(options: Options, src: &str) -> String {
+    let mut out = String::new();
+
+    Tester::run(|t| {
+        let syntax = ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+            jsx: true,
+            ..Default::default()
+        });
+        let module = t.with_parser("input.js", syntax, src, |p| {
+            p.parse_module().map_err(|mut e| {
+                e.emit();
+            })
+        })?;
+        let module = COMMENTS.set(&t.comments, || module.fold_with(&mut jsx(options)));
+
+        out = t.print_with_comments(&module);
+        Ok(())
+    });
+
+    out
+}
+
+/// Returns `(pure comments, pure comments followed by a call to callee)`.
+fn count_pure(out: &str, callee: &str) -> (usize, usize) {
+    let cmts = out.split("/*#__PURE__*/").skip(1);
+    let total = cmts.clone().count();
+    let before_callee = cmts
+        .filter(|s| s.trim_start().starts_with(&format!("{}(", callee)))
+        .count();
+
+    (total, before_callee)
+}
+
+#[test]
+fn pure_annotations() {
+    let out = print_with_comments(Default::default(), "var a = <div><span /><><b /></></div>;");
+
+    assert_eq!(count_pure(&out, "React.createElement"), (4, 4), "{}", out);
+}
+
+#[test]
+fn pure_annotations_automatic() {
+    let out = print_with_comments(
+        Options {
+            runtime: Runtime::Automatic,
+            ..Default::default()
+        },
+        "var a = <div><span /></div>;
+var b = <><i /><b /></>;",
+    );
+
+    assert_eq!(count_pure(&out, "_jsx"), (5, 4), "{}", out);
+    assert_eq!(count_pure(&out, "_jsxs"), (5, 1), "{}", out);
+}
+
+#[test]
+fn pure_annotations_disabled() {
+    let out = print_with_comments(
+        Options {
+            pure: false,
+            ..Default::default()
+        },
+        "var a = <div><span /></div>;",
+    );
+
+    assert_eq!(count_pure(&out, "React.createElement"), (0, 0), "{}", out);
+}
+
+#[test]
+fn pure_annotations_without_comments() {
+    Tester::run(|t| {
+        let syntax = ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+            jsx: true,
+            ..Default::default()
+        });
+        let module = t.with_parser("input.js", syntax, "var a = <div />;", |p| {
+            p.parse_module().map_err(|mut e| {
+                e.emit();
+            })
+        })?;
+        module.fold_with(&mut jsx(Default::default()));
+
+        Ok(())
+    });
+}
+
+/// Returns true if `jsx(options)` reports an error for `src`, which is parsed
+/// as a script if `script` is true.
+fn reports_error(options: Options, src: &str, script: bool) -> bool {
+    let mut has_errors = false;
+
+    Tester::run(|t| {
+        let syntax = ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+            jsx: true,
+            ..Default::default()
+        });
+        if script {
+            let script = t.with_parser("input.js", syntax, src, |p| {
+                p.parse_script().map_err(|mut e| {
+                    e.emit();
+                })
+            })?;
+            script.fold_with(&mut jsx(options));
+        } else {
+            let module = t.with_parser("input.js", syntax, src, |p| {
+                p.parse_module().map_err(|mut e| {
+                    e.emit();
+                })
+            })?;
+            module.fold_with(&mut jsx(options));
+        }
+
+        has_errors = t.handler.has_errors();
+        Ok(())
+    });
+
+    has_errors
+}
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |_| AsScript(jsx(Default::default())),
+    script_classic,
+    "var a = <div />;",
+    r#"var a = React.createElement("div", null);"#
+);
+
+#[test]
+fn script_automatic() {
+    assert!(reports_error(
+        Options {
+            runtime: Runtime::Automatic,
+            ..Default::default()
+        },
+        "var a = <div />;",
+        true,
+    ));
+}
@@ -1,6 +1,7 @@
 use crate::pass::Pass;
 use ast::*;
-use swc_common::{Fold, DUMMY_SP};
+use std::mem::replace;
+use swc_common::{Fold, FoldWith, DUMMY_SP};
 
 #[cfg(test)]
 mod tests;
@@ -8,18 +9,109 @@ mod tests;
 /// `@babel/plugin-transform-react-jsx-self`
 ///
 /// Add a __self prop to all JSX Elements
-pub fn jsx_self(dev: bool) -> impl Pass {
-    JsxSelf { dev }
+///
+/// Elements where `this` is not usable (top level and constructors of derived
+/// classes) are left as is.
+pub fn jsx_self(dev: bool, options: DevOptions) -> impl Pass {
+    JsxSelf {
+        dev,
+        options,
+        this_allowed: false,
+        in_derived_class: false,
+    }
+}
+
+/// Options for passes adding dev-only attributes (`jsx_self` and `jsx_src`).
+#[derive(Default)]
+pub struct DevOptions {
+    /// Elements are left as is if this returns false for their names.
+    pub filter: Option<Box<dyn Fn(&JSXElementName) -> bool>>,
+}
+
+impl DevOptions {
+    /// Returns true if an attribute named `attr` should be added to `e`.
+    pub(super) fn should_add(&self, e: &JSXOpeningElement, attr: &str) -> bool {
+        let has_attr = e.attrs.iter().any(|a| match *a {
+            JSXAttrOrSpread::JSXAttr(JSXAttr {
+                name: JSXAttrName::Ident(ref i),
+                ..
+            }) => i.sym == *attr,
+            _ => false,
+        });
+        if has_attr {
+            return false;
+        }
+
+        match self.filter {
+            Some(ref filter) => filter(&e.name),
+            None => true,
+        }
+    }
 }
+
 struct JsxSelf {
     dev: bool,
+    options: DevOptions,
+    this_allowed: bool,
+    /// True if the nearest class has a super class.
+    in_derived_class: bool,
+}
+
+impl JsxSelf {
+    fn fold_with_this<T>(&mut self, this_allowed: bool, node: T) -> T
+    where
+        T: FoldWith<Self>,
+    {
+        let old = replace(&mut self.this_allowed, this_allowed);
+        let node = node.fold_children(self);
+        self.this_allowed = old;
+        node
+    }
 }
 
+impl Fold<Class> for JsxSelf {
+    fn fold(&mut self, c: Class) -> Class {
+        let old = replace(&mut self.in_derived_class, c.super_class.is_some());
+        let c = c.fold_children(self);
+        self.in_derived_class = old;
+        c
+    }
+}
+
+impl Fold<Constructor> for JsxSelf {
+    fn fold(&mut self, c: Constructor) -> Constructor {
+        // `this` may be used before `super()`
+        let this_allowed = !self.in_derived_class;
+        self.fold_with_this(this_allowed, c)
+    }
+}
+
+macro_rules! this_allowed {
+    ($T:ty) => {
+        impl Fold<$T> for JsxSelf {
+            fn fold(&mut self, node: $T) -> $T {
+                self.fold_with_this(true, node)
+            }
+        }
+    };
+}
+
+this_allowed!(Function);
+this_allowed!(GetterProp);
+this_allowed!(SetterProp);
+this_allowed!(ClassProp);
+this_allowed!(PrivateProp);
+
 impl Fold<JSXOpeningElement> for JsxSelf {
-    fn fold(&mut self, mut n: JSXOpeningElement) -> JSXOpeningElement {
+    fn fold(&mut self, n: JSXOpeningElement) -> JSXOpeningElement {
         if !self.dev {
             return n;
         }
+        let mut n = n.fold_children(self);
+
+        if !self.this_allowed || !self.options.should_add(&n, "__self") {
+            return n;
+        }
 
         n.attrs.push(JSXAttrOrSpread::JSXAttr(JSXAttr {
             span: DUMMY_SP,
@@ -1,7 +1,7 @@
 use super::*;
 
 fn tr() -> impl Fold<Module> {
-    jsx_self(true)
+    jsx_self(true, Default::default())
 }
 
 test!(
@@ -11,6 +11,132 @@ test!(
     }),
     |_| tr(),
     basic_sample,
-    r#"var x = <sometag />"#,
-    r#"var x = <sometag __self={this} />;"#
+    r#"var x = function () {
+    return <sometag />;
+};"#,
+    r#"var x = function () {
+    return <sometag __self={this} />;
+};"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |_| tr(),
+    top_level,
+    r#"var x = <sometag />;
+var y = () => <sometag />;"#,
+    r#"var x = <sometag />;
+var y = () => <sometag />;"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        class_props: true,
+        ..Default::default()
+    }),
+    |_| tr(),
+    class_members,
+    r#"class Foo {
+    a = <a />;
+    constructor() {
+        this.b = () => <b />;
+    }
+    render() {
+        return <c />;
+    }
+    get d() {
+        return <d />;
+    }
+}"#,
+    r#"class Foo {
+    a = <a __self={this} />;
+    constructor() {
+        this.b = () => <b __self={this} />;
+    }
+    render() {
+        return <c __self={this} />;
+    }
+    get d() {
+        return <d __self={this} />;
+    }
+}"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |_| tr(),
+    derived_constructor,
+    r#"class Foo extends Bar {
+    constructor() {
+        super(<a />);
+        class Baz {
+            constructor() {
+                this.b = <b />;
+            }
+        }
+    }
+    render() {
+        return <c />;
+    }
+}"#,
+    r#"class Foo extends Bar {
+    constructor() {
+        super(<a />);
+        class Baz {
+            constructor() {
+                this.b = <b __self={this} />;
+            }
+        }
+    }
+    render() {
+        return <c __self={this} />;
+    }
+}"#
+);
+
+/// Skips host elements like `<div>`.
+fn skip_lowercase() -> DevOptions {
+    DevOptions {
+        filter: Some(box |name: &JSXElementName| match *name {
+            JSXElementName::Ident(ref i) => !i.sym.starts_with(|c: char| c.is_ascii_lowercase()),
+            _ => true,
+        }),
+    }
+}
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |_| jsx_self(true, skip_lowercase()),
+    filter,
+    r#"function render() {
+    return <div><MyComp /><lib.Wrapper /><></></div>;
+}"#,
+    r#"function render() {
+    return <div><MyComp __self={this} /><lib.Wrapper __self={this} /><></></div>;
+}"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |_| tr(),
+    existing_self,
+    r#"function render() {
+    return <MyComp __self={other} />;
+}"#,
+    r#"function render() {
+    return <MyComp __self={other} />;
+}"#
 );
@@ -1,30 +1,130 @@
-use crate::pass::Pass;
+use super::DevOptions;
+use crate::{pass::Pass, util::prepend};
 use ast::*;
 use std::sync::Arc;
-use swc_common::{FileName, Fold, SourceMap, DUMMY_SP};
+use swc_atoms::JsWord;
+use swc_common::{Fold, FoldWith, SourceMap, DUMMY_SP};
 
 #[cfg(test)]
 mod tests;
 
 /// `@babel/plugin-transform-react-jsx-source`
-pub fn jsx_src(dev: bool, cm: Arc<SourceMap>) -> impl Pass {
-    JsxSrc { cm, dev }
+///
+/// Adds `__source={{ fileName, lineNumber, columnNumber }}` to all JSX
+/// elements. The file name is stored in a variable named `_jsxFileName`.
+///
+/// This pass should run before `jsx`.
+pub fn jsx_src(dev: bool, cm: Arc<SourceMap>, options: DevOptions) -> impl Pass {
+    JsxSrc {
+        cm,
+        dev,
+        options,
+        file_name: None,
+    }
 }
 
 struct JsxSrc {
     cm: Arc<SourceMap>,
     dev: bool,
+    options: DevOptions,
+    /// `(file name, _jsxFileName)`
+    file_name: Option<(JsWord, Ident)>,
+}
+
+impl JsxSrc {
+    /// Returns the identifier of the variable holding `file_name`.
+    fn file_name_ident(&mut self, file_name: JsWord) -> Expr {
+        match self.file_name {
+            Some((ref name, ref ident)) if *name == file_name => Expr::Ident(ident.clone()),
+            // An element from another file. Not possible in practice.
+            Some(..) => Expr::Lit(Lit::Str(Str {
+                span: DUMMY_SP,
+                value: file_name,
+                has_escape: false,
+            })),
+            None => {
+                let ident = private_ident!("_jsxFileName");
+                self.file_name = Some((file_name, ident.clone()));
+                Expr::Ident(ident)
+            }
+        }
+    }
+}
+
+impl JsxSrc {
+    /// Returns `var _jsxFileName = "..."` if any element references it.
+    fn take_file_name_decl(&mut self) -> Option<Stmt> {
+        let (file_name, ident) = self.file_name.take()?;
+
+        Some(Stmt::Decl(Decl::Var(VarDecl {
+            span: DUMMY_SP,
+            kind: VarDeclKind::Var,
+            declare: false,
+            decls: vec![VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(ident),
+                init: Some(box Expr::Lit(Lit::Str(Str {
+                    span: DUMMY_SP,
+                    value: file_name,
+                    has_escape: false,
+                }))),
+                definite: false,
+            }],
+        })))
+    }
+}
+
+impl Fold<Module> for JsxSrc {
+    fn fold(&mut self, module: Module) -> Module {
+        if !self.dev {
+            return module;
+        }
+
+        let mut module = module.fold_children(self);
+
+        if let Some(decl) = self.take_file_name_decl() {
+            prepend(&mut module.body, ModuleItem::Stmt(decl));
+        }
+
+        module
+    }
+}
+
+impl Fold<Script> for JsxSrc {
+    fn fold(&mut self, script: Script) -> Script {
+        if !self.dev {
+            return script;
+        }
+
+        let mut script = script.fold_children(self);
+
+        if let Some(decl) = self.take_file_name_decl() {
+            prepend(&mut script.body, decl);
+        }
+
+        script
+    }
 }
 
 impl Fold<JSXOpeningElement> for JsxSrc {
-    fn fold(&mut self, mut e: JSXOpeningElement) -> JSXOpeningElement {
+    fn fold(&mut self, e: JSXOpeningElement) -> JSXOpeningElement {
         if !self.dev || e.span == DUMMY_SP {
             return e;
         }
+        let mut e = e.fold_children(self);
+
+        if !self.options.should_add(&e, "__source") {
+            return e;
+        }
 
-        let file_lines = match self.cm.span_to_lines(e.span) {
-            Ok(v) => v,
-            _ => return e,
+        let loc = self.cm.lookup_char_pos(e.span.lo());
+        let file_name = self.file_name_ident(loc.file.name.to_string().into());
+
+        let num = |value: usize| {
+            box Expr::Lit(Lit::Num(Number {
+                span: DUMMY_SP,
+                value: value as _,
+            }))
         };
 
         e.attrs.push(JSXAttrOrSpread::JSXAttr(JSXAttr {
@@ -38,21 +138,15 @@ impl Fold<JSXOpeningElement> for JsxSrc {
                         props: vec![
                             PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
                                 key: PropName::Ident(quote_ident!("fileName")),
-                                value: box Expr::Lit(Lit::Str(Str {
-                                    span: DUMMY_SP,
-                                    value: match file_lines.file.name {
-                                        FileName::Real(ref p) => p.display().to_string().into(),
-                                        _ => unimplemented!("file name for other than real files"),
-                                    },
-                                    has_escape: false,
-                                })),
+                                value: box file_name,
                             })),
                             PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
                                 key: PropName::Ident(quote_ident!("lineNumber")),
-                                value: box Expr::Lit(Lit::Num(Number {
-                                    span: DUMMY_SP,
-                                    value: (file_lines.lines[0].line_index + 1) as _,
-                                })),
+                                value: num(loc.line),
+                            })),
+                            PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                                key: PropName::Ident(quote_ident!("columnNumber")),
+                                value: num(loc.col.0 + 1),
                             })),
                         ],
                     }
@@ -1,64 +1,114 @@
 use super::*;
-use swc_common::FilePathMapping;
+use crate::tests::AsScript;
 
-fn tr() -> impl Fold<Module> {
-    let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
-    jsx_src(true, cm)
-}
-
-test_exec!(
-    ignore,
+fn syntax() -> ::swc_ecma_parser::Syntax {
     ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
         jsx: true,
         ..Default::default()
-    }),
-    |_| tr(),
-    basic_sample,
-    r#"
-var actual = transform(
-  'var x = <sometag />',
-  Object.assign({}, opts, { filename: '/fake/path/mock.js' })
-).code;
-
-var expected = multiline([
-  'var _jsxFileName = "/fake/path/mock.js";',
-  'var x = <sometag __source={{',
-  '  fileName: _jsxFileName,',
-  '  lineNumber: 1',
-  '}} />;',
-]);
+    })
+}
 
-expect(actual).toBe(expected);
-"#
+test!(
+    syntax(),
+    |t| jsx_src(true, t.cm.clone(), Default::default()),
+    basic_sample,
+    r#"var x = <sometag />"#,
+    r#"var _jsxFileName = "input.js";
+var x = <sometag __source={{
+  fileName: _jsxFileName,
+  lineNumber: 1,
+  columnNumber: 9
+}} />;"#
 );
 
 test!(
-    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
-        jsx: true,
-        ..Default::default()
-    }),
-    |_| tr(),
+    syntax(),
+    |t| jsx_src(true, t.cm.clone(), Default::default()),
     no_jsx,
     r#"var x = 42;"#,
     r#"var x = 42;"#
 );
 
-test_exec!(
-    ignore,
-    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
-        jsx: true,
-        ..Default::default()
-    }),
-    |_| tr(),
+test!(
+    syntax(),
+    |t| jsx_src(true, t.cm.clone(), Default::default()),
     with_source,
-    r#"
-var actual = transform(
-  'var x = <sometag __source="custom" />;',
-  Object.assign({}, opts, { filename: '/fake/path/mock.js' })
-).code;
+    r#"var x = <sometag __source="custom" />;"#,
+    r#"var x = <sometag __source="custom" />;"#
+);
+
+test!(
+    syntax(),
+    |t| jsx_src(false, t.cm.clone(), Default::default()),
+    not_dev,
+    r#"var x = <sometag />;"#,
+    r#"var x = <sometag />;"#
+);
+
+test!(
+    syntax(),
+    |t| jsx_src(true, t.cm.clone(), Default::default()),
+    multiple_lines,
+    r#"var x = (
+  <div>
+    <span />
+  </div>
+);
+var y = <b a={<i />} />;"#,
+    r#"var _jsxFileName = "input.js";
+var x = <div __source={{
+    fileName: _jsxFileName,
+    lineNumber: 2,
+    columnNumber: 3
+  }}>
+    <span __source={{
+      fileName: _jsxFileName,
+      lineNumber: 3,
+      columnNumber: 5
+    }} />
+  </div>;
+var y = <b a={<i __source={{
+  fileName: _jsxFileName,
+  lineNumber: 6,
+  columnNumber: 15
+}} />} __source={{
+  fileName: _jsxFileName,
+  lineNumber: 6,
+  columnNumber: 9
+}} />;"#
+);
 
-var expected = 'var x = <sometag __source="custom" />;';
+test!(
+    syntax(),
+    |t| jsx_src(
+        true,
+        t.cm.clone(),
+        DevOptions {
+            filter: Some(box |name: &JSXElementName| match *name {
+                JSXElementName::Ident(ref i) => i.sym != *"div",
+                _ => true,
+            }),
+        }
+    ),
+    filter,
+    r#"var x = <div><MyComp /></div>;"#,
+    r#"var _jsxFileName = "input.js";
+var x = <div><MyComp __source={{
+  fileName: _jsxFileName,
+  lineNumber: 1,
+  columnNumber: 14
+}} /></div>;"#
+);
 
-expect(actual).toBe(expected);
-"#
+test!(
+    syntax(),
+    |t| AsScript(jsx_src(true, t.cm.clone(), Default::default())),
+    script,
+    r#"var x = <sometag />;"#,
+    r#"var _jsxFileName = "input.js";
+var x = <sometag __source={{
+  fileName: _jsxFileName,
+  lineNumber: 1,
+  columnNumber: 9
+}} />;"#
 );
@@ -0,0 +1,176 @@
+use super::jsx::{parse_option, Options, Runtime};
+use crate::{pass::Pass, util::undefined};
+use ast::*;
+use swc_atoms::JsWord;
+use swc_common::{Fold, FoldWith, DUMMY_SP};
+
+#[cfg(test)]
+mod tests;
+
+/// Removes `__self`, `__source` and attributes named `names` from jsx
+/// elements.
+///
+/// Elements already lowered by `jsx` with the same `options` are handled too,
+/// so this pass should run before `hygiene`. Spreads are not modified.
+pub fn strip_dev_props(options: Options, names: Vec<JsWord>) -> impl Pass {
+    StripDevProps {
+        matcher: ElementCallMatcher::new(&options),
+        names,
+    }
+}
+
+struct StripDevProps {
+    matcher: ElementCallMatcher,
+    names: Vec<JsWord>,
+}
+
+impl StripDevProps {
+    fn should_strip(&self, name: &str) -> bool {
+        name == "__self" || name == "__source" || self.names.iter().any(|n| *n == *name)
+    }
+}
+
+impl Fold<JSXOpeningElement> for StripDevProps {
+    fn fold(&mut self, e: JSXOpeningElement) -> JSXOpeningElement {
+        let mut e = e.fold_children(self);
+
+        e.attrs.retain(|attr| match *attr {
+            JSXAttrOrSpread::JSXAttr(JSXAttr {
+                name: JSXAttrName::Ident(ref i),
+                ..
+            }) => !self.should_strip(&i.sym),
+            _ => true,
+        });
+
+        e
+    }
+}
+
+impl Fold<CallExpr> for StripDevProps {
+    fn fold(&mut self, call: CallExpr) -> CallExpr {
+        let mut call = call.fold_children(self);
+
+        let kind = match self.matcher.kind(&call.callee) {
+            Some(kind) => kind,
+            None => return call,
+        };
+
+        // `_jsxDEV(type, props, key, isStaticChildren, source, self)`
+        if kind == ElementCall::AutomaticDev {
+            for arg in call.args.iter_mut().skip(4) {
+                if arg.spread.is_none() {
+                    arg.expr = undefined(DUMMY_SP);
+                }
+            }
+        }
+
+        let props = match call.args.get_mut(1) {
+            Some(&mut ExprOrSpread {
+                spread: None,
+                ref mut expr,
+            }) => expr,
+            _ => return call,
+        };
+
+        let is_empty = match **props {
+            // Keep `{}` as is.
+            Expr::Object(ObjectLit { props: ref obj, .. }) if obj.is_empty() => false,
+            Expr::Object(ObjectLit { props: ref mut obj, .. }) => {
+                obj.retain(|prop| match *prop {
+                    PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp { ref key, .. })) => {
+                        match *key {
+                            PropName::Ident(ref i) => !self.should_strip(&i.sym),
+                            PropName::Str(ref s) => !self.should_strip(&s.value),
+                            _ => true,
+                        }
+                    }
+                    _ => true,
+                });
+
+                obj.is_empty()
+            }
+            _ => false,
+        };
+
+        // `React.createElement('div', {})` => `React.createElement('div', null)`
+        //
+        // The automatic runtime requires an object.
+        if is_empty && kind == ElementCall::Classic {
+            *props = box Expr::Lit(Lit::Null(Null { span: DUMMY_SP }));
+        }
+
+        call
+    }
+}
+
+/// Kind of a call creating an element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ElementCall {
+    /// `React.createElement(type, props, ...children)`
+    Classic,
+    /// `_jsx(type, props, key)` or `_jsxs(type, props, key)`
+    Automatic,
+    /// `_jsxDEV(type, props, key, isStaticChildren, source, self)`
+    AutomaticDev,
+}
+
+/// Matches calls created by `jsx`.
+pub(super) struct ElementCallMatcher {
+    pragma: Box<Expr>,
+    runtime: Runtime,
+}
+
+impl ElementCallMatcher {
+    pub fn new(options: &Options) -> Self {
+        ElementCallMatcher {
+            pragma: parse_option("pragma", options.pragma.clone()),
+            runtime: options.runtime,
+        }
+    }
+
+    /// Returns `None` if `callee` does not create an element.
+    pub fn kind(&self, callee: &ExprOrSuper) -> Option<ElementCall> {
+        let callee = match *callee {
+            ExprOrSuper::Expr(ref callee) => &**callee,
+            _ => return None,
+        };
+
+        match self.runtime {
+            Runtime::Classic if is_same_name(&self.pragma, callee) => Some(ElementCall::Classic),
+            Runtime::Classic => None,
+            Runtime::Automatic => match *callee {
+                Expr::Ident(ref i) => match &*i.sym {
+                    "_jsx" | "_jsxs" => Some(ElementCall::Automatic),
+                    "_jsxDEV" => Some(ElementCall::AutomaticDev),
+                    // Used for a key after a spread.
+                    "_createElement" => Some(ElementCall::Classic),
+                    _ => None,
+                },
+                _ => None,
+            },
+        }
+    }
+}
+
+/// Returns true if `callee` is `name`, which is an identifier or a member
+/// expression like `React.createElement`.
+fn is_same_name(name: &Expr, callee: &Expr) -> bool {
+    match (name, callee) {
+        (&Expr::Ident(ref name), &Expr::Ident(ref callee)) => name.sym == callee.sym,
+        (
+            &Expr::Member(MemberExpr {
+                obj: ExprOrSuper::Expr(ref name_obj),
+                prop: ref name_prop,
+                computed: false,
+                ..
+            }),
+            &Expr::Member(MemberExpr {
+                obj: ExprOrSuper::Expr(ref callee_obj),
+                prop: ref callee_prop,
+                computed: false,
+                ..
+            }),
+        ) => is_same_name(name_prop, callee_prop) && is_same_name(name_obj, callee_obj),
+        _ => false,
+    }
+}
@@ -0,0 +1,180 @@
+use super::*;
+use crate::react::{jsx, jsx_self, Options, Runtime};
+use swc_common::chain;
+
+fn tr() -> impl Fold<Module> {
+    strip_dev_props(Default::default(), vec!["data-testid".into()])
+}
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |_| tr(),
+    jsx_attrs,
+    r#"<div __self={this} __source={{ fileName: "a.js" }} data-testid="root" {...props}>
+    <span data-testid="child" id="a" />
+</div>;"#,
+    r#"<div {...props}>
+    <span id="a" />
+</div>;"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |_| tr(),
+    create_element,
+    r#"React.createElement("div", {
+    __self: this,
+    id: "a",
+    "data-testid": "root"
+});
+React.createElement("div", {
+    __self: this,
+    __source: source
+}, React.createElement("span", {
+    "data-testid": "child"
+}));
+React.createElement("div", {});"#,
+    r#"React.createElement("div", {
+    id: "a"
+});
+React.createElement("div", null, React.createElement("span", null));
+React.createElement("div", {});"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |_| tr(),
+    spread_untouched,
+    r#"React.createElement("div", {
+    ...props,
+    __self: this
+});
+React.createElement("div", _extends({}, props, {
+    __self: this
+}));
+foo("div", {
+    __self: this
+});"#,
+    r#"React.createElement("div", {
+    ...props
+});
+React.createElement("div", _extends({}, props, {
+    __self: this
+}));
+foo("div", {
+    __self: this
+});"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |_| chain!(
+        jsx_self(true, Default::default()),
+        jsx(Default::default()),
+        strip_dev_props(Default::default(), vec![])
+    ),
+    after_jsx,
+    r#"function Foo() {
+    return <div />;
+}"#,
+    r#"function Foo() {
+    return React.createElement("div", null);
+}"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |_| strip_dev_props(
+        Options {
+            pragma: "h".into(),
+            ..Default::default()
+        },
+        vec![]
+    ),
+    custom_pragma,
+    r#"h("div", {
+    __self: this
+});
+React.createElement("div", {
+    __self: this
+});"#,
+    r#"h("div", null);
+React.createElement("div", {
+    __self: this
+});"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::default(),
+    |_| strip_dev_props(
+        Options {
+            pragma: "preact.h".into(),
+            ..Default::default()
+        },
+        vec![]
+    ),
+    custom_member_pragma,
+    r#"preact.h("div", {
+    __self: this
+});
+h("div", {
+    __self: this
+});"#,
+    r#"preact.h("div", null);
+h("div", {
+    __self: this
+});"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |_| {
+        let options = Options {
+            runtime: Runtime::Automatic,
+            ..Default::default()
+        };
+        chain!(
+            jsx(options.clone()),
+            strip_dev_props(options, vec!["data-testid".into()])
+        )
+    },
+    automatic,
+    r#"<div data-testid="root" id="a"><span data-testid="child" /></div>;"#,
+    r#"import { jsx as _jsx } from "react/jsx-runtime";
+_jsx("div", {
+    id: "a",
+    children: _jsx("span", {})
+});"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |_| {
+        let options = Options {
+            runtime: Runtime::Automatic,
+            development: true,
+            ..Default::default()
+        };
+        chain!(
+            jsx_self(true, Default::default()),
+            jsx(options.clone()),
+            strip_dev_props(options, vec![])
+        )
+    },
+    automatic_dev,
+    r#"<div key="a" />;"#,
+    r#"import { jsxDEV as _jsxDEV } from "react/jsx-dev-runtime";
+_jsxDEV("div", {}, "a", false, void 0, void 0);"#
+);
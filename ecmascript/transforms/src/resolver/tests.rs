@@ -7,7 +7,7 @@ use swc_common::chain;
 use swc_ecma_parser::{EsConfig, Syntax};
 
 fn tr() -> impl Fold<Module> {
-    chain!(resolver(), block_scoping())
+    chain!(resolver(), block_scoping(Default::default()))
 }
 
 fn syntax() -> Syntax {
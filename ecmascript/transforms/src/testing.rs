@@ -0,0 +1,317 @@
+//! Utilities to verify output of transforms.
+//!
+//! # Syntax levels
+//!
+//!  - es5: getters and setters.
+//!  - es2015: arrow functions, classes, generators, `let` / `const`, template
+//!    literals, spread elements, `for-of`, destructuring, rest elements,
+//!    default values, shorthand / method / computed properties, `new.target`
+//!    and sticky or unicode regexes.
+//!  - es2016: exponentiation operator.
+//!  - es2017: async functions.
+//!  - es2018: async generators, `for-await-of`, object rest / spread and dotAll
+//!    regexes.
+//!  - es2019: optional catch binding.
+//!  - es2020: optional chaining, nullish coalescing, bigint, dynamic import and
+//!    `import.meta`.
+//!
+//! Module declarations are not reported, as they are handled by module
+//! transforms instead of compat transforms.
+
+use crate::util::ExprExt;
+use ast::*;
+use swc_atoms::js_word;
+use swc_common::{Span, Spanned, Visit, VisitWith};
+use swc_ecma_parser::JscTarget;
+
+/// A node which requires a newer version of ecmascript than expected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub span: Span,
+    /// Human readable name of the node.
+    pub node: &'static str,
+    /// Minimum version which supports the node.
+    pub version: JscTarget,
+}
+
+/// Returns all nodes in `module` which are not supported by `version`.
+///
+/// This walks the ast instead of reparsing the printed code, so it can be
+/// used to verify output of a chain of passes in tests.
+pub fn assert_no_syntax_above(module: &Module, version: JscTarget) -> Vec<Violation> {
+    let mut v = SyntaxLevel {
+        max: version,
+        violations: vec![],
+    };
+    module.visit_with(&mut v);
+    v.violations
+}
+
+struct SyntaxLevel {
+    max: JscTarget,
+    violations: Vec<Violation>,
+}
+
+impl SyntaxLevel {
+    fn report(&mut self, span: Span, node: &'static str, version: JscTarget) {
+        if version > self.max {
+            self.violations.push(Violation {
+                span,
+                node,
+                version,
+            })
+        }
+    }
+}
+
+impl Visit<Expr> for SyntaxLevel {
+    fn visit(&mut self, e: &Expr) {
+        let span = e.span();
+
+        match *e {
+            Expr::Arrow(ArrowExpr { is_async, .. }) => {
+                self.report(span, "arrow function", JscTarget::Es2015);
+                if is_async {
+                    self.report(span, "async arrow function", JscTarget::Es2017);
+                }
+            }
+            Expr::Class(..) => self.report(span, "class expression", JscTarget::Es2015),
+            Expr::Tpl(..) => self.report(span, "template literal", JscTarget::Es2015),
+            Expr::TaggedTpl(..) => self.report(span, "tagged template", JscTarget::Es2015),
+            Expr::MetaProp(MetaPropExpr { ref meta, .. }) => {
+                if meta.sym == js_word!("import") {
+                    self.report(span, "import.meta", JscTarget::Es2020)
+                } else {
+                    self.report(span, "new.target", JscTarget::Es2015)
+                }
+            }
+            Expr::OptChain(..) => self.report(span, "optional chaining", JscTarget::Es2020),
+            Expr::Bin(BinExpr { op, .. }) => match op {
+                op!("**") => self.report(span, "exponentiation operator", JscTarget::Es2016),
+                op!("??") => self.report(span, "nullish coalescing", JscTarget::Es2020),
+                _ => {}
+            },
+            Expr::Assign(AssignExpr { op, .. }) => {
+                if op == op!("**=") {
+                    self.report(span, "exponentiation operator", JscTarget::Es2016)
+                }
+            }
+            Expr::Lit(Lit::BigInt(..)) => self.report(span, "bigint", JscTarget::Es2020),
+            Expr::Lit(Lit::Regex(Regex { ref flags, .. })) => {
+                if flags.contains('y') || flags.contains('u') {
+                    self.report(span, "sticky or unicode regex", JscTarget::Es2015)
+                }
+                if flags.contains('s') {
+                    self.report(span, "dotAll regex", JscTarget::Es2018)
+                }
+            }
+            Expr::Call(CallExpr {
+                callee: ExprOrSuper::Expr(ref callee),
+                ..
+            }) if callee.is_ident_ref_to(js_word!("import")) => {
+                self.report(span, "dynamic import", JscTarget::Es2020)
+            }
+            _ => {}
+        }
+
+        e.visit_children(self)
+    }
+}
+
+impl Visit<Function> for SyntaxLevel {
+    fn visit(&mut self, f: &Function) {
+        match (f.is_async, f.is_generator) {
+            (true, true) => self.report(f.span, "async generator function", JscTarget::Es2018),
+            (true, false) => self.report(f.span, "async function", JscTarget::Es2017),
+            (false, true) => self.report(f.span, "generator function", JscTarget::Es2015),
+            (false, false) => {}
+        }
+
+        f.visit_children(self)
+    }
+}
+
+impl Visit<ClassDecl> for SyntaxLevel {
+    fn visit(&mut self, c: &ClassDecl) {
+        self.report(c.class.span, "class declaration", JscTarget::Es2015);
+
+        c.visit_children(self)
+    }
+}
+
+impl Visit<VarDecl> for SyntaxLevel {
+    fn visit(&mut self, v: &VarDecl) {
+        match v.kind {
+            VarDeclKind::Var => {}
+            VarDeclKind::Let => self.report(v.span, "let declaration", JscTarget::Es2015),
+            VarDeclKind::Const => self.report(v.span, "const declaration", JscTarget::Es2015),
+        }
+
+        v.visit_children(self)
+    }
+}
+
+impl Visit<ForOfStmt> for SyntaxLevel {
+    fn visit(&mut self, s: &ForOfStmt) {
+        if s.await_token.is_some() {
+            self.report(s.span, "for-await-of statement", JscTarget::Es2018)
+        } else {
+            self.report(s.span, "for-of statement", JscTarget::Es2015)
+        }
+
+        s.visit_children(self)
+    }
+}
+
+impl Visit<CatchClause> for SyntaxLevel {
+    fn visit(&mut self, c: &CatchClause) {
+        if c.param.is_none() {
+            self.report(c.span, "optional catch binding", JscTarget::Es2019)
+        }
+
+        c.visit_children(self)
+    }
+}
+
+impl Visit<Pat> for SyntaxLevel {
+    fn visit(&mut self, p: &Pat) {
+        match *p {
+            Pat::Array(ref p) => self.report(p.span, "array pattern", JscTarget::Es2015),
+            Pat::Object(ref p) => self.report(p.span, "object pattern", JscTarget::Es2015),
+            Pat::Rest(ref p) => self.report(p.span(), "rest element", JscTarget::Es2015),
+            Pat::Assign(ref p) => self.report(p.span, "default value", JscTarget::Es2015),
+            _ => {}
+        }
+
+        p.visit_children(self)
+    }
+}
+
+impl Visit<ObjectPatProp> for SyntaxLevel {
+    fn visit(&mut self, p: &ObjectPatProp) {
+        if let ObjectPatProp::Rest(ref p) = *p {
+            self.report(p.span(), "object rest", JscTarget::Es2018)
+        }
+
+        p.visit_children(self)
+    }
+}
+
+impl Visit<ExprOrSpread> for SyntaxLevel {
+    fn visit(&mut self, e: &ExprOrSpread) {
+        if let Some(spread) = e.spread {
+            self.report(spread, "spread element", JscTarget::Es2015)
+        }
+
+        e.visit_children(self)
+    }
+}
+
+impl Visit<PropOrSpread> for SyntaxLevel {
+    fn visit(&mut self, p: &PropOrSpread) {
+        match *p {
+            PropOrSpread::Spread(ref s) => {
+                self.report(s.dot3_token, "object spread", JscTarget::Es2018)
+            }
+            PropOrSpread::Prop(ref prop) => match **prop {
+                Prop::Shorthand(ref i) => {
+                    self.report(i.span, "shorthand property", JscTarget::Es2015)
+                }
+                Prop::Method(ref m) => {
+                    self.report(m.function.span, "method property", JscTarget::Es2015)
+                }
+                Prop::Getter(ref g) => self.report(g.span, "getter", JscTarget::Es5),
+                Prop::Setter(ref s) => self.report(s.span, "setter", JscTarget::Es5),
+                _ => {}
+            },
+        }
+
+        p.visit_children(self)
+    }
+}
+
+impl Visit<PropName> for SyntaxLevel {
+    fn visit(&mut self, n: &PropName) {
+        if let PropName::Computed(ref c) = *n {
+            self.report(c.span, "computed property", JscTarget::Es2015)
+        }
+
+        n.visit_children(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        compat::{es2015, es2016},
+        pass::{noop, Pass},
+        tests::Tester,
+    };
+    use swc_common::chain;
+    use swc_ecma_parser::{EsConfig, Syntax};
+
+    fn violations(tr: impl Pass, src: &str, version: JscTarget) -> Vec<(&'static str, JscTarget)> {
+        let mut result = vec![];
+        Tester::run(|tester| {
+            let module = tester.apply_transform(
+                tr,
+                "input.js",
+                Syntax::Es(EsConfig {
+                    dynamic_import: true,
+                    nullish_coalescing: true,
+                    ..Default::default()
+                }),
+                src,
+            )?;
+
+            result = assert_no_syntax_above(&module, version)
+                .into_iter()
+                .map(|v| (v.node, v.version))
+                .collect();
+            Ok(())
+        });
+        result
+    }
+
+    #[test]
+    fn input_level() {
+        assert_eq!(
+            violations(
+                noop(),
+                "const f = async (a, ...b) => a ?? import('x');",
+                JscTarget::Es2015
+            ),
+            vec![
+                ("async arrow function", JscTarget::Es2017),
+                ("nullish coalescing", JscTarget::Es2020),
+                ("dynamic import", JscTarget::Es2020),
+            ]
+        );
+    }
+
+    #[test]
+    fn es5_output() {
+        assert_eq!(
+            violations(
+                chain!(es2016(), es2015(Default::default())),
+                "class A { m(a = 1) { return [...a] ** 2; } }",
+                JscTarget::Es5
+            ),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn leaky_chain() {
+        // es2016 is missing, so the exponentiation operator leaks.
+        assert_eq!(
+            violations(
+                es2015(Default::default()),
+                "class A { m(a = 1) { return [...a] ** 2; } }",
+                JscTarget::Es5
+            ),
+            vec![("exponentiation operator", JscTarget::Es2016)]
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{
     helpers::{InjectHelpers, HELPERS},
     pass::Pass,
+    util::COMMENTS,
 };
 use ast::*;
 use sourcemap::SourceMapBuilder;
@@ -120,14 +121,19 @@ impl<'a> Tester<'a> {
                 handler: &self.handler,
             };
 
-            let mut p = Parser::new(sess, syntax, SourceFileInput::from(&*fm), None);
+            let mut p = Parser::new(
+                sess,
+                syntax,
+                SourceFileInput::from(&*fm),
+                Some(&self.comments),
+            );
             p.parse_module().map_err(|mut e| {
                 e.emit();
             })?
         };
 
-        let module = validate!(module)
-            .fold_with(&mut tr)
+        let module = COMMENTS
+            .set(&self.comments, || validate!(module).fold_with(&mut tr))
             .fold_with(&mut ::testing::DropSpan)
             .fold_with(&mut Normalizer);
 
@@ -135,6 +141,15 @@ impl<'a> Tester<'a> {
     }
 
     pub fn print(&mut self, module: &Module) -> String {
+        self.print_inner(module, None)
+    }
+
+    /// Prints `module` with comments, including ones added by transforms.
+    pub fn print_with_comments(&mut self, module: &Module) -> String {
+        self.print_inner(module, Some(&self.comments))
+    }
+
+    fn print_inner(&self, module: &Module, comments: Option<&Comments>) -> String {
         let handlers = box MyHandlers;
 
         let mut wr = Buf(Arc::new(RwLock::new(vec![])));
@@ -149,7 +164,7 @@ impl<'a> Tester<'a> {
                     &mut wr,
                     Some(&mut src_map_builder),
                 ),
-                comments: None,
+                comments,
                 handlers,
             };
 
@@ -163,6 +178,38 @@ impl<'a> Tester<'a> {
     }
 }
 
+/// Applies the pass to the module as a script, so that `test!` can check
+/// `Fold<Script>` of a pass.
+pub(crate) struct AsScript<P>(pub P);
+
+impl<P> Fold<Module> for AsScript<P>
+where
+    P: Fold<Script>,
+{
+    fn fold(&mut self, module: Module) -> Module {
+        let body = module
+            .body
+            .into_iter()
+            .map(|item| match item {
+                ModuleItem::Stmt(stmt) => stmt,
+                ModuleItem::ModuleDecl(..) => panic!("a script cannot contain module declarations"),
+            })
+            .collect();
+
+        let script = self.0.fold(Script {
+            span: module.span,
+            body,
+            shebang: module.shebang,
+        });
+
+        Module {
+            span: script.span,
+            body: script.body.into_iter().map(ModuleItem::Stmt).collect(),
+            shebang: script.shebang,
+        }
+    }
+}
+
 fn make_tr<F, P>(_: &'static str, op: F, tester: &mut Tester<'_>) -> impl Pass
 where
     F: FnOnce(&mut Tester<'_>) -> P,
@@ -6,7 +6,7 @@
 use swc_common::chain;
 use swc_ecma_parser::{EsConfig, Syntax};
 use swc_ecma_transforms::{
-    compat::es2015::{arrow, block_scoping, spread, Classes},
+    compat::es2015::{arrow, block_scoping, classes, spread, Classes},
     pass::Pass,
     react::jsx,
     resolver,
@@ -23,6 +23,10 @@ fn tr() -> impl Pass {
     Classes::default()
 }
 
+fn loose_tr() -> impl Pass {
+    classes(classes::Config { loose: true })
+}
+
 fn spec_tr() -> impl Pass {
     chain!(
         resolver(),
@@ -30,7 +34,7 @@ fn spec_tr() -> impl Pass {
         spread(spread::Config {
             ..Default::default()
         }),
-        block_scoping(),
+        block_scoping(Default::default()),
     )
 }
 
@@ -5180,7 +5184,7 @@ function (Array) {
 // extend_builtins_imported_babel_plugin_transform_builtin_classes
 test_exec!(
     syntax(),
-    |_| chain!(Classes::default(), block_scoping()),
+    |_| chain!(Classes::default(), block_scoping(Default::default())),
     extend_builtins_imported_babel_plugin_transform_builtin_classes_exec,
     r#"
 // Imported from
@@ -5481,7 +5485,7 @@ expect(obj.test).toBe(3);
 // extend_builtins_spec
 test_exec!(
     syntax(),
-    |_| chain!(Classes::default(), block_scoping()),
+    |_| chain!(Classes::default(), block_scoping(Default::default())),
     extend_builtins_spec_exec,
     r#"
 class List extends Array {}
@@ -5731,7 +5735,7 @@ test!(
     // TODO: Unignore this
     ignore,
     syntax(),
-    |_| chain!(tr(), block_scoping()),
+    |_| chain!(tr(), block_scoping(Default::default())),
     regression_t7010,
     r#"
 class Foo {
@@ -6018,7 +6022,7 @@ expect(obj.test).toBe(3);
 // extend_builtins_builtin_objects_throw_when_wrapped
 test_exec!(
     syntax(),
-    |_| chain!(Classes::default(), block_scoping()),
+    |_| chain!(Classes::default(), block_scoping(Default::default())),
     extend_builtins_builtin_objects_throw_when_wrapped_exec,
     r#"
 // JSON is wrapped because it starts with an uppercase letter, but it
@@ -6067,7 +6071,7 @@ test_exec!(
     // Just don't do this.
     ignore,
     syntax(),
-    |_| chain!(Classes::default(), block_scoping()),
+    |_| chain!(Classes::default(), block_scoping(Default::default())),
     extend_builtins_overwritten_null_exec,
     r#"
 var env = {
@@ -6088,7 +6092,7 @@ test_exec!(
     // Just don't do this. With is evil.
     ignore,
     syntax(),
-    |_| chain!(Classes::default(), block_scoping()),
+    |_| chain!(Classes::default(), block_scoping(Default::default())),
     extend_builtins_super_called_exec,
     r#"
 var called = false;
@@ -6109,3 +6113,156 @@ with (env) {
 
 "#
 );
+
+const HIERARCHY: &str = "
+class Animal {
+    constructor(name) {
+        this.name = name;
+    }
+    speak() {
+        return this.name;
+    }
+    static create(name) {
+        return new Animal(name);
+    }
+    get upper() {
+        return this.name.toUpperCase();
+    }
+}
+class Dog extends Animal {
+    speak() {
+        return 'woof';
+    }
+}
+";
+
+test!(
+    syntax(),
+    |_| tr(),
+    hierarchy_spec,
+    HIERARCHY,
+    "
+let Animal = function() {
+    'use strict';
+    function Animal(name) {
+        _classCallCheck(this, Animal);
+        this.name = name;
+    }
+    _createClass(Animal, [{
+        key: 'speak',
+        value: function speak() {
+            return this.name;
+        }
+    }, {
+        key: 'upper',
+        get: function() {
+            return this.name.toUpperCase();
+        }
+    }], [{
+        key: 'create',
+        value: function create(name) {
+            return new Animal(name);
+        }
+    }]);
+    return Animal;
+}();
+let Dog = function(Animal) {
+    'use strict';
+    _inherits(Dog, Animal);
+    function Dog() {
+        _classCallCheck(this, Dog);
+        return _possibleConstructorReturn(this, _getPrototypeOf(Dog).apply(this, arguments));
+    }
+    _createClass(Dog, [{
+        key: 'speak',
+        value: function speak() {
+            return 'woof';
+        }
+    }]);
+    return Dog;
+}(Animal);
+"
+);
+
+test!(
+    syntax(),
+    |_| loose_tr(),
+    hierarchy_loose,
+    HIERARCHY,
+    "
+let Animal = function() {
+    'use strict';
+    function Animal(name) {
+        this.name = name;
+    }
+    Animal.prototype.speak = function speak() {
+        return this.name;
+    };
+    Animal.create = function create(name) {
+        return new Animal(name);
+    };
+    _createClass(Animal, [{
+        key: 'upper',
+        get: function() {
+            return this.name.toUpperCase();
+        }
+    }]);
+    return Animal;
+}();
+let Dog = function(Animal) {
+    'use strict';
+    _inheritsLoose(Dog, Animal);
+    function Dog() {
+        return _possibleConstructorReturn(this, _getPrototypeOf(Dog).apply(this, arguments));
+    }
+    Dog.prototype.speak = function speak() {
+        return 'woof';
+    };
+    return Dog;
+}(Animal);
+"
+);
+
+test!(
+    syntax(),
+    |_| loose_tr(),
+    loose_computed_keys,
+    "
+class Foo {
+    [bar]() {}
+    'baz'() {}
+    static 1() {}
+}
+",
+    "
+let Foo = function() {
+    'use strict';
+    function Foo() {
+    }
+    Foo.prototype[bar] = function() {
+    };
+    Foo.prototype['baz'] = function baz() {
+    };
+    Foo[1] = function() {
+    };
+    return Foo;
+}();
+"
+);
+
+test_exec!(
+    syntax(),
+    |_| loose_tr(),
+    hierarchy_loose_exec,
+    &format!(
+        "{}
+const dog = new Dog('rex');
+expect(dog.speak()).toBe('woof');
+expect(dog.upper).toBe('REX');
+expect(dog instanceof Animal).toBe(true);
+expect(Animal.create('cat').speak()).toBe('cat');
+expect(Object.keys(Animal.prototype)).toEqual(['speak']);
+expect(Object.getPrototypeOf(Dog)).toBe(Animal);",
+        HIERARCHY
+    )
+);
@@ -4,7 +4,10 @@
 #![feature(specialization)]
 
 use swc_ecma_parser::Syntax;
-use swc_ecma_transforms::{compat::es2015::computed_properties, pass::Pass};
+use swc_ecma_transforms::{
+    compat::es2015::{computed_properties, computed_props::Config},
+    pass::Pass,
+};
 
 #[macro_use]
 mod common;
@@ -14,12 +17,12 @@ fn syntax() -> Syntax {
 }
 
 fn tr(_: ()) -> impl Pass {
-    computed_properties()
+    computed_properties(Default::default())
 }
 
 test!(
     ::swc_ecma_parser::Syntax::default(),
-    |_| computed_properties(),
+    |_| computed_properties(Default::default()),
     issue_210,
     "
 const b = {[a]: 1}
@@ -33,7 +36,7 @@ export const c = _defineProperty({
 
 test!(
     ::swc_ecma_parser::Syntax::default(),
-    |_| computed_properties(),
+    |_| computed_properties(Default::default()),
     accessors,
     r#"var obj = {
   get [foobar]() {
@@ -50,27 +53,22 @@ test!(
   }
 };
 "#,
-    r#"var _obj, _mutatorMap = {
-};
+    r#"var _obj;
 var obj = ( _obj = {
-}, _mutatorMap[foobar] = _mutatorMap[foobar] || {
-}, _mutatorMap[foobar].get = function() {
+}, _defineAccessor('get', _obj, foobar, function() {
     return 'foobar';
-}, _mutatorMap[foobar] = _mutatorMap[foobar] || {
-}, _mutatorMap[foobar].set = function(x) {
+}), _defineAccessor('set', _obj, foobar, function(x) {
     console.log(x);
-}, _mutatorMap['test'] = _mutatorMap['test'] || {
-}, _mutatorMap['test'].get = function() {
+}), _defineAccessor('get', _obj, 'test', function() {
     return 'regular getter after computed property';
-}, _mutatorMap['test'] = _mutatorMap['test'] || {
-}, _mutatorMap['test'].set = function(x) {
+}), _defineAccessor('set', _obj, 'test', function(x) {
     console.log(x);
-}, _defineEnumerableProperties(_obj, _mutatorMap), _obj);"#
+}), _obj);"#
 );
 
 test!(
     ::swc_ecma_parser::Syntax::default(),
-    |_| computed_properties(),
+    |_| computed_properties(Default::default()),
     argument,
     r#"foo({
   [bar]: "foobar"
@@ -81,7 +79,7 @@ test!(
 
 test!(
     ::swc_ecma_parser::Syntax::default(),
-    |_| computed_properties(),
+    |_| computed_properties(Default::default()),
     assignment,
     r#"foo = {
   [bar]: "foobar"
@@ -91,7 +89,7 @@ test!(
 
 test!(
     ::swc_ecma_parser::Syntax::default(),
-    |_| computed_properties(),
+    |_| computed_properties(Default::default()),
     method,
     r#"var obj = {
   [foobar]() {
@@ -112,7 +110,7 @@ var obj = (_obj = {}, _defineProperty(_obj, foobar, function () {
 
 test!(
     ::swc_ecma_parser::Syntax::default(),
-    |_| computed_properties(),
+    |_| computed_properties(Default::default()),
     mixed,
     r#"var obj = {
   ["x" + foo]: "heh",
@@ -128,7 +126,7 @@ var obj = (_obj = {}, _defineProperty(_obj, "x" + foo, "heh"), _defineProperty(_
 
 test!(
     ::swc_ecma_parser::Syntax::default(),
-    |_| computed_properties(),
+    |_| computed_properties(Default::default()),
     multiple,
     r#"var obj = {
   ["x" + foo]: "heh",
@@ -142,7 +140,7 @@ _defineProperty(_obj, "y" + bar, "noo"), _obj);"#
 
 test!(
     ::swc_ecma_parser::Syntax::default(),
-    |_| computed_properties(),
+    |_| computed_properties(Default::default()),
     single,
     r#"var obj = {
   ["x" + foo]: "heh"
@@ -152,7 +150,7 @@ test!(
 
 test!(
     ::swc_ecma_parser::Syntax::default(),
-    |_| computed_properties(),
+    |_| computed_properties(Default::default()),
     symbol,
     r#"var k = Symbol();
 var foo = {
@@ -163,18 +161,16 @@ var foo = {
 };
 "#,
     r#"var k = Symbol();
-var _obj, _mutatorMap = {
-};
+var _obj;
 var foo = ( _obj = {
-}, _defineProperty(_obj, Symbol.iterator, 'foobar'), _mutatorMap[k] = _mutatorMap[k] || {
-}, _mutatorMap[k].get = function() {
+}, _defineProperty(_obj, Symbol.iterator, 'foobar'), _defineAccessor('get', _obj, k, function() {
     return k;
-}, _defineEnumerableProperties(_obj, _mutatorMap), _obj);"#
+}), _obj);"#
 );
 
 test_exec!(
     ::swc_ecma_parser::Syntax::default(),
-    |_| computed_properties(),
+    |_| computed_properties(Default::default()),
     symbol_exec,
     r#"
 var k = Symbol();
@@ -191,7 +187,7 @@ expect(foo[k]).toBe(k)"#
 
 test!(
     ::swc_ecma_parser::Syntax::default(),
-    |_| computed_properties(),
+    |_| computed_properties(Default::default()),
     this,
     r#"var obj = {
   ["x" + foo.bar]: "heh"
@@ -201,7 +197,7 @@ test!(
 
 test!(
     ::swc_ecma_parser::Syntax::default(),
-    |_| computed_properties(),
+    |_| computed_properties(Default::default()),
     issue_315_1,
     "
 ({
@@ -231,7 +227,7 @@ export function corge() {
 
 test!(
     ::swc_ecma_parser::Syntax::default(),
-    |_| computed_properties(),
+    |_| computed_properties(Default::default()),
     issue_315_2,
     "
 export function corge() {}
@@ -243,7 +239,7 @@ export function corge() {}
 
 test!(
     ::swc_ecma_parser::Syntax::default(),
-    |_| computed_properties(),
+    |_| computed_properties(Default::default()),
     issue_315_3,
     "
 export function corge() {}
@@ -274,7 +270,7 @@ _defineProperty({
 
 test!(
     ::swc_ecma_parser::Syntax::default(),
-    |_| computed_properties(),
+    |_| computed_properties(Default::default()),
     issue_315_4,
     "
 export class Foo {}
@@ -518,3 +514,181 @@ expect(foo[k]).toBe(k)
 
 "#
 );
+
+test!(
+    syntax(),
+    |_| computed_properties(Default::default()),
+    single_accessor,
+    r#"var obj = {
+  a: 1,
+  get [k]() {
+    return 2;
+  }
+};"#,
+    r#"var obj = _defineAccessor("get", {
+  a: 1
+}, k, function () {
+  return 2;
+});"#
+);
+
+test_exec!(
+    syntax(),
+    |_| computed_properties(Default::default()),
+    mixed_accessors_exec,
+    r#"
+var value = 0;
+var obj = {
+  a: 1,
+  get ["b"]() {
+    return value;
+  },
+  c: 3,
+  set ["b"](v) {
+    value = v;
+  },
+  get d() {
+    return 4;
+  }
+};
+
+obj.b = 2;
+expect(obj.b).toBe(2);
+expect(obj.d).toBe(4);
+expect(Object.keys(obj)).toEqual(["a", "b", "c", "d"]);
+
+var desc = Object.getOwnPropertyDescriptor(obj, "b");
+expect(desc.enumerable).toBe(true);
+expect(desc.configurable).toBe(true);
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| computed_properties(Default::default()),
+    source_order_exec,
+    r#"
+var log = [];
+function key(k) {
+  log.push("key " + k);
+  return k;
+}
+function val(v) {
+  log.push("value " + v);
+  return v;
+}
+
+var obj = {
+  [key("a")]: val(1),
+  b: val(2),
+  get [key("c")]() {
+    return 3;
+  },
+  [key("d")]: val(4),
+  [key("a")]: val(5)
+};
+
+expect(log).toEqual([
+  "key a",
+  "value 1",
+  "value 2",
+  "key c",
+  "key d",
+  "value 4",
+  "key a",
+  "value 5"
+]);
+expect(Object.keys(obj)).toEqual(["a", "b", "c", "d"]);
+expect(obj.a).toBe(5);
+expect(obj.c).toBe(3);
+"#
+);
+
+test!(
+    syntax(),
+    |_| computed_properties(Config { loose: true }),
+    loose,
+    r#"var obj = {
+  [a]: 1,
+  b: 2,
+  get [c]() {
+    return 3;
+  }
+};"#,
+    r#"var _obj;
+var obj = (_obj = {}, _obj[a] = 1, _obj["b"] = 2, _defineAccessor("get", _obj, c, function () {
+  return 3;
+}), _obj);"#
+);
+
+test_exec!(
+    syntax(),
+    |_| computed_properties(Config { loose: true }),
+    loose_exec,
+    r#"
+var obj = {
+  ["x" + 1]: 1,
+  y: 2,
+  get ["z"]() {
+    return 3;
+  }
+};
+
+expect(obj).toEqual({ x1: 1, y: 2, z: 3 });
+"#
+);
+
+test!(
+    syntax(),
+    |_| computed_properties(Config { loose: true }),
+    proto,
+    r#"var obj = {
+  [a]: 1,
+  ["__proto__"]: b,
+  __proto__: c
+};"#,
+    r#"var _obj;
+var obj = (_obj = {}, _obj[a] = 1, _defineProperty(_obj, "__proto__", b), _obj.__proto__ = c, _obj);"#
+);
+
+test_exec!(
+    syntax(),
+    |_| computed_properties(Default::default()),
+    proto_exec,
+    r#"
+var proto = { inherited: true };
+var obj = {
+  ["a"]: 1,
+  ["__proto__"]: proto
+};
+
+expect(Object.getPrototypeOf(obj)).toBe(Object.prototype);
+expect(Object.prototype.hasOwnProperty.call(obj, "__proto__")).toBe(true);
+expect(obj.inherited).toBe(undefined);
+
+var obj2 = {
+  ["a"]: 1,
+  __proto__: proto
+};
+
+expect(Object.getPrototypeOf(obj2)).toBe(proto);
+expect(Object.prototype.hasOwnProperty.call(obj2, "__proto__")).toBe(false);
+expect(obj2.inherited).toBe(true);
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| computed_properties(Config { loose: true }),
+    loose_proto_exec,
+    r#"
+var proto = { inherited: true };
+var obj = {
+  ["a"]: 1,
+  ["__proto__"]: proto
+};
+
+expect(Object.getPrototypeOf(obj)).toBe(Object.prototype);
+expect(Object.prototype.hasOwnProperty.call(obj, "__proto__")).toBe(true);
+"#
+);
@@ -595,7 +595,7 @@ test!(
         }),
         parameters(),
         destructuring(Default::default()),
-        block_scoping(),
+        block_scoping(Default::default()),
         object_rest_spread(),
     ),
     destructuring_for_of,
@@ -630,7 +630,7 @@ test!(
         }),
         parameters(),
         destructuring(Default::default()),
-        block_scoping(),
+        block_scoping(Default::default()),
         object_rest_spread(),
     ),
     destructuring_object_basic,
@@ -656,7 +656,7 @@ test!(
         }),
         parameters(),
         destructuring(Default::default()),
-        block_scoping(),
+        block_scoping(Default::default()),
         object_rest_spread(),
     ),
     destructuring_assignment_arrow_function_block,
@@ -681,7 +681,7 @@ test_exec!(
         }),
         parameters(),
         destructuring(Default::default()),
-        block_scoping(),
+        block_scoping(Default::default()),
         object_rest_spread(),
     ),
     destructuring_non_iterable_exec,
@@ -708,7 +708,7 @@ test_exec!(
         }),
         parameters(),
         destructuring(Default::default()),
-        block_scoping(),
+        block_scoping(Default::default()),
         object_rest_spread(),
     ),
     destructuring_empty_object_pattern_exec,
@@ -729,7 +729,7 @@ test_exec!(
         }),
         parameters(),
         destructuring(Default::default()),
-        block_scoping(),
+        block_scoping(Default::default()),
         object_rest_spread(),
     ),
     destructuring_chained_exec,
@@ -754,7 +754,7 @@ test_exec!(
         }),
         parameters(),
         destructuring(Default::default()),
-        block_scoping(),
+        block_scoping(Default::default()),
     ),
     destructuring_object_rest_impure_computed_keys_exec,
     r#"
@@ -805,7 +805,7 @@ test_exec!(
         }),
         parameters(),
         destructuring(Default::default()),
-        block_scoping(),
+        block_scoping(Default::default()),
         object_rest_spread(),
     ),
     destructuring_issue_5090_exec,
@@ -832,7 +832,7 @@ test_exec!(
         }),
         parameters(),
         destructuring(Default::default()),
-        block_scoping(),
+        block_scoping(Default::default()),
         object_rest_spread(),
     ),
     destructuring_default_precedence_exec,
@@ -867,7 +867,7 @@ expect(f2({a: 1})).toEqual([1, 1, 1]);
 //    [destructuring(Default::default()), { "useBuiltIns": true }],
 //    spread(spread::Config{..Default::default()}),
 //    parameters(),
-//    block_scoping(),
+//    block_scoping(Default::default()),
 //    object_rest_spread(),
 //  ]
 //}
@@ -917,7 +917,7 @@ test!(
         }),
         parameters(),
         destructuring(Default::default()),
-        block_scoping(),
+        block_scoping(Default::default()),
         object_rest_spread(),
     ),
     destructuring_parameters,
@@ -984,7 +984,7 @@ test!(
         }),
         parameters(),
         destructuring(Default::default()),
-        block_scoping(),
+        block_scoping(Default::default()),
         object_rest_spread(),
     ),
     destructuring_array_unpack_optimisation,
@@ -1065,7 +1065,7 @@ test!(
         }),
         parameters(),
         destructuring(Default::default()),
-        block_scoping(),
+        block_scoping(Default::default()),
         object_rest_spread(),
     ),
     destructuring_known_array,
@@ -1092,7 +1092,7 @@ test!(
         }),
         parameters(),
         destructuring(Default::default()),
-        block_scoping(),
+        block_scoping(Default::default()),
         object_rest_spread(),
     ),
     destructuring_es7_object_rest,
@@ -1153,7 +1153,7 @@ test!(
         }),
         parameters(),
         destructuring(Default::default()),
-        block_scoping(),
+        block_scoping(Default::default()),
     ),
     destructuring_assignment_expression_pattern,
     r#"
@@ -1177,7 +1177,7 @@ test!(
         }),
         parameters(),
         destructuring(Default::default()),
-        block_scoping(),
+        block_scoping(Default::default()),
     ),
     destructuring_object_advanced,
     r#"
@@ -1211,7 +1211,7 @@ test!(
         }),
         parameters(),
         destructuring(Default::default()),
-        block_scoping(),
+        block_scoping(Default::default()),
     ),
     destructuring_spread,
     r#"
@@ -1247,7 +1247,7 @@ test!(
         }),
         parameters(),
         destructuring(Default::default()),
-        block_scoping(),
+        block_scoping(Default::default()),
     ),
     destructuring_mixed,
     r#"
@@ -1276,7 +1276,7 @@ test!(
         spread(spread::Config {
             ..Default::default()
         }),
-        block_scoping(),
+        block_scoping(Default::default()),
         object_rest_spread()
     ),
     destructuring_assignment_statement,
@@ -1298,7 +1298,7 @@ test!(
         }),
         parameters(),
         destructuring(Default::default()),
-        block_scoping(),
+        block_scoping(Default::default()),
         object_rest_spread(),
     ),
     destructuring_array,
@@ -1323,7 +1323,7 @@ test!(
         }),
         parameters(),
         destructuring(Default::default()),
-        block_scoping(),
+        block_scoping(Default::default()),
         object_rest_spread(),
     ),
     destructuring_assignment_arrow_function_no_block,
@@ -1347,7 +1347,7 @@ test!(
         }),
         parameters(),
         destructuring(Default::default()),
-        block_scoping(),
+        block_scoping(Default::default()),
         object_rest_spread(),
     ),
     destructuring_issue_9834,
@@ -1409,7 +1409,7 @@ test!(
         }),
         parameters(),
         destructuring(Default::default()),
-        block_scoping(),
+        block_scoping(Default::default()),
         object_rest_spread(),
     ),
     destructuring_for_in,
@@ -1444,7 +1444,7 @@ test!(
         }),
         parameters(),
         destructuring(Default::default()),
-        block_scoping(),
+        block_scoping(Default::default()),
         object_rest_spread(),
     ),
     destructuring_issue_5744,
@@ -1468,7 +1468,7 @@ test_exec!(
         }),
         parameters(),
         destructuring(Default::default()),
-        block_scoping(),
+        block_scoping(Default::default()),
         object_rest_spread(),
     ),
     destructuring_spread_generator_exec,
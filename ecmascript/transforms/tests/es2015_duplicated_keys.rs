@@ -3,8 +3,9 @@
 #![feature(box_patterns)]
 #![feature(specialization)]
 
+use swc_common::chain;
 use swc_ecma_parser::Syntax;
-use swc_ecma_transforms::compat::es2015::duplicate_keys;
+use swc_ecma_transforms::compat::es2015::{computed_properties, duplicate_keys};
 
 #[macro_use]
 mod common;
@@ -314,3 +315,105 @@ var x = {
 
 "#
 );
+
+test!(
+    syntax(),
+    |_| duplicate_keys(),
+    getter_setter_pair,
+    r#"
+var obj = {
+  get x() {
+    return 1;
+  },
+  set x(v) {},
+  y: 1,
+  get y() {
+    return 2;
+  }
+};
+"#,
+    r#"
+var obj = {
+  get x() {
+    return 1;
+  },
+  set x(v) {},
+  y: 1,
+  get ["y"]() {
+    return 2;
+  }
+};
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| chain!(duplicate_keys(), computed_properties(Default::default())),
+    getter_setter_pair_exec,
+    r#"
+var value = 0;
+var obj = {
+  get x() {
+    return value;
+  },
+  set x(v) {
+    value = v;
+  }
+};
+
+obj.x = 5;
+expect(obj.x).toBe(5);
+expect(value).toBe(5);
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| chain!(duplicate_keys(), computed_properties(Default::default())),
+    data_then_accessor_exec,
+    r#"
+var obj = {
+  x: 1,
+  get x() {
+    return 2;
+  },
+  get y() {
+    return 3;
+  },
+  y: 4
+};
+
+expect(obj.x).toBe(2);
+expect(Object.getOwnPropertyDescriptor(obj, "x").set).toBe(undefined);
+expect(obj.y).toBe(4);
+expect(Object.getOwnPropertyDescriptor(obj, "y").get).toBe(undefined);
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| chain!(duplicate_keys(), computed_properties(Default::default())),
+    true_duplicates_exec,
+    r#"
+var a = 5;
+var obj = {
+  a: 1,
+  "a": 2,
+  a,
+  b: 3,
+  get c() {
+    return 1;
+  },
+  set c(v) {},
+  get c() {
+    return 4;
+  }
+};
+
+expect(obj.a).toBe(5);
+expect(obj.b).toBe(3);
+expect(obj.c).toBe(4);
+expect(typeof Object.getOwnPropertyDescriptor(obj, "c").set).toBe("function");
+expect(Object.keys(obj)).toEqual(["a", "b", "c"]);
+"#
+);
@@ -4,12 +4,14 @@
 #![feature(specialization)]
 
 use swc_common::chain;
-use swc_ecma_parser::Syntax;
+use swc_ecma_parser::{EsConfig, Syntax};
 use swc_ecma_transforms::{
-    compat::es2015::{arrow, block_scoping, classes::Classes, function_name, Shorthand},
+    compat::es2015::{
+        arrow, block_scoping, classes::Classes, computed_properties, function_name, Shorthand,
+    },
     modules::{amd::amd, common_js::common_js, umd::umd},
     pass::Pass,
-    proposals::decorators,
+    proposals::{class_properties, decorators},
     resolver,
 };
 
@@ -21,13 +23,7 @@ fn syntax() -> Syntax {
 }
 
 fn tr() -> impl Pass {
-    chain!(resolver(), function_name(), block_scoping())
-}
-
-macro_rules! identical {
-    ($name:ident, $src:literal) => {
-        test!(syntax(), |_| tr(), $name, $src, $src);
-    };
+    chain!(resolver(), function_name(), block_scoping(Default::default()))
 }
 
 test!(
@@ -1299,3 +1295,308 @@ var j = function j1() {
 };
 "#
 );
+
+test!(
+    syntax(),
+    |_| tr(),
+    object_property,
+    r#"
+var obj = {
+  foo: function () {},
+  "bar": function () {},
+  "not-ident": function () {},
+  baz: class {}
+};
+"#,
+    r#"
+var obj = {
+  foo: function foo() {},
+  "bar": function bar() {},
+  "not-ident": function () {},
+  baz: class baz {}
+};
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| chain!(tr(), computed_properties(Default::default())),
+    object_property_exec,
+    r#"
+var obj = {
+  foo: function () {},
+  "bar": function () {},
+  [1 + 1]: 0,
+  baz: function () {}
+};
+
+expect(obj.foo.name).toBe("foo");
+expect(obj.bar.name).toBe("bar");
+expect(obj.baz.name).toBe("baz");
+"#
+);
+
+test_exec!(
+    Syntax::Es(EsConfig {
+        class_props: true,
+        ..Default::default()
+    }),
+    |_| chain!(resolver(), function_name(), class_properties()),
+    class_property_exec,
+    r#"
+class Foo {
+  foo = function () {};
+  "bar" = function () {};
+  static baz = function () {};
+}
+
+expect(new Foo().foo.name).toBe("foo");
+expect(new Foo().bar.name).toBe("bar");
+expect(Foo.baz.name).toBe("baz");
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| chain!(resolver(), arrow(), function_name()),
+    arrow_exec,
+    r#"
+var foo = () => {};
+var obj = {
+  bar: () => {}
+};
+
+expect(foo.name).toBe("foo");
+expect(obj.bar.name).toBe("bar");
+"#
+);
+
+test!(
+    syntax(),
+    |_| chain!(resolver(), arrow(), function_name()),
+    export_default,
+    r#"
+export default () => {};
+"#,
+    r#"
+export default function _default() {}
+"#,
+    ok_if_code_eq
+);
+
+test!(
+    syntax(),
+    |_| chain!(resolver(), function_name()),
+    export_default_fn_decl,
+    r#"
+export default function () {}
+"#,
+    r#"
+export default function _default() {}
+"#,
+    ok_if_code_eq
+);
+
+test!(
+    syntax(),
+    |_| chain!(resolver(), function_name()),
+    export_default_class_decl,
+    r#"
+export default class {}
+"#,
+    r#"
+export default class _default {}
+"#,
+    ok_if_code_eq
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    object_property_shadowing,
+    r#"
+function foo() {}
+var obj = {
+  foo: function () {
+    return foo();
+  },
+  bar: function ({ bar }) {
+    return bar;
+  }
+};
+"#,
+    r#"
+function foo() {}
+var obj = {
+  foo: function () {
+    return foo();
+  },
+  bar: function bar({ bar: bar1 }) {
+    return bar1;
+  }
+};
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| tr(),
+    object_property_shadowing_exec,
+    r#"
+function foo() {
+  return 1;
+}
+var obj = {
+  foo: function (n) {
+    return n ? foo() : 2;
+  }
+};
+
+expect(obj.foo(true)).toBe(1);
+expect(obj.foo(false)).toBe(2);
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| tr(),
+    var_shadowing_exec,
+    r#"
+var f = function (n) {
+  return n ? f(false) : 1;
+};
+var g = f;
+f = function () {
+  return 2;
+};
+
+expect(g(true)).toBe(2);
+"#
+);
+
+test!(
+    syntax(),
+    |_| chain!(resolver(), function_name(), common_js(Default::default())),
+    common_js_export_default,
+    "export default function () {
+  return 1;
+}
+export var char = function () {};",
+    r#"
+"use strict";
+
+Object.defineProperty(exports, "__esModule", {
+  value: true
+});
+exports.char = void 0;
+
+function _default() {
+  return 1;
+}
+
+exports.default = _default;
+
+var char = function _char() {};
+
+exports.char = char;
+"#
+);
+
+test!(
+    syntax(),
+    |_| chain!(resolver(), function_name(), common_js(Default::default())),
+    common_js_live_binding,
+    "export let handler = function () {};
+export function reset() {
+  handler = function () {};
+}",
+    r#"
+"use strict";
+
+Object.defineProperty(exports, "__esModule", {
+  value: true
+});
+exports.reset = reset;
+exports.handler = void 0;
+
+let handler = function handler() {};
+
+exports.handler = handler;
+
+function reset() {
+  exports.handler = handler = function handler() {};
+}
+"#
+);
+
+test!(
+    syntax(),
+    |_| chain!(resolver(), function_name(), common_js(Default::default())),
+    common_js_export_after_assignment,
+    "let handler;
+handler = function () {};
+export { handler as onLoad };",
+    r#"
+"use strict";
+
+Object.defineProperty(exports, "__esModule", {
+  value: true
+});
+exports.onLoad = void 0;
+let handler;
+exports.onLoad = handler = function handler() {};
+exports.onLoad = handler;
+"#
+);
+
+test!(
+    syntax(),
+    |_| chain!(
+        resolver(),
+        function_name(),
+        Classes::default(),
+        common_js(Default::default())
+    ),
+    common_js_class_self_reference,
+    "export let Foo = class {
+  reset() {
+    Foo = null;
+  }
+};
+export let Bar = class {};",
+    r#"
+"use strict";
+
+Object.defineProperty(exports, "__esModule", {
+  value: true
+});
+exports.Bar = exports.Foo = void 0;
+
+let Foo = function () {
+  "use strict";
+
+  function _class() {
+    _classCallCheck(this, _class);
+  }
+
+  _createClass(_class, [{
+    key: "reset",
+    value: function reset() {
+      exports.Foo = Foo = null;
+    }
+  }]);
+
+  return _class;
+}();
+
+exports.Foo = Foo;
+
+let Bar = function Bar() {
+  "use strict";
+
+  _classCallCheck(this, Bar);
+};
+
+exports.Bar = Bar;
+"#
+);
@@ -27,7 +27,7 @@ fn tr() -> impl Pass {
         resolver(),
         parameters(),
         swc_ecma_transforms::compat::es2015::destructuring(destructuring::Config { loose: false }),
-        swc_ecma_transforms::compat::es2015::block_scoping(),
+        swc_ecma_transforms::compat::es2015::block_scoping(Default::default()),
     )
 }
 
@@ -1523,7 +1523,7 @@ test!(
         Classes::default(),
         parameters(),
         destructuring(Default::default()),
-        block_scoping(),
+        block_scoping(Default::default()),
         common_js(Default::default()),
     ),
     regression_4209,
@@ -1662,7 +1662,7 @@ function foo() {
 // parameters_regression_4333
 test!(
     syntax(),
-    |_| chain!(parameters(), block_scoping(),),
+    |_| chain!(parameters(), block_scoping(Default::default()),),
     parameters_regression_4333,
     r#"
 const args = 'bar';
@@ -1018,3 +1018,149 @@ test_exec!(
     expect(() => v.next()).toThrow('2')
     "
 );
+
+test_exec!(
+    syntax(),
+    |_| tr(Default::default()),
+    delegate_return_value,
+    "
+function* inner() {
+    const x = yield 1;
+    yield x;
+    return 'r';
+}
+
+function* outer() {
+    const r = yield* inner();
+    yield r;
+    yield* [4, 5];
+}
+
+expect(Array.from(outer())).toEqual([1, undefined, 'r', 4, 5]);
+"
+);
+
+test_exec!(
+    syntax(),
+    |_| tr(Default::default()),
+    try_catch_finally_yield,
+    "
+const log = [];
+function* gen() {
+    try {
+        yield 1;
+        throw new Error('x');
+    } catch (e) {
+        log.push(e.message);
+        yield 2;
+    } finally {
+        log.push('f');
+        yield 3;
+    }
+    return 4;
+}
+
+const it = gen();
+expect(it.next()).toEqual({ value: 1, done: false });
+expect(it.next()).toEqual({ value: 2, done: false });
+expect(it.next()).toEqual({ value: 3, done: false });
+expect(it.next()).toEqual({ value: 4, done: true });
+expect(log).toEqual(['x', 'f']);
+"
+);
+
+test_exec!(
+    syntax(),
+    |_| tr(Default::default()),
+    return_runs_finally,
+    "
+const log = [];
+function* gen() {
+    try {
+        yield 1;
+        yield 2;
+    } finally {
+        log.push('cleanup');
+    }
+}
+
+const it = gen();
+it.next();
+expect(it.return(5)).toEqual({ value: 5, done: true });
+expect(log).toEqual(['cleanup']);
+"
+);
+
+test_exec!(
+    syntax(),
+    |_| tr(Default::default()),
+    yield_in_loop_test,
+    "
+function* gen() {
+    let i = 0;
+    while ((yield i) !== 'stop') {
+        i++;
+    }
+    return i;
+}
+
+const it = gen();
+expect(it.next().value).toBe(0);
+expect(it.next().value).toBe(1);
+expect(it.next().value).toBe(2);
+expect(it.next('stop')).toEqual({ value: 2, done: true });
+"
+);
+
+test_exec!(
+    syntax(),
+    |_| tr(Default::default()),
+    labeled_continue_across_yield,
+    "
+function* gen() {
+    outer: for (let i = 0; i < 3; i++) {
+        for (let j = 0; j < 3; j++) {
+            yield [i, j];
+            if (j === 1) continue outer;
+        }
+    }
+}
+
+expect(Array.from(gen())).toEqual([[0, 0], [0, 1], [1, 0], [1, 1], [2, 0], [2, 1]]);
+"
+);
+
+test_exec!(
+    syntax(),
+    |_| tr(Default::default()),
+    labeled_break_across_yield,
+    "
+function* gen() {
+    outer: for (let i = 0; i < 3; i++) {
+        for (let j = 0; j < 3; j++) {
+            if (i === 1) break outer;
+            yield [i, j];
+        }
+    }
+}
+
+expect(Array.from(gen())).toEqual([[0, 0], [0, 1], [0, 2]]);
+"
+);
+
+test_exec!(
+    syntax(),
+    |_| tr(Default::default()),
+    early_return_value,
+    "
+function* gen(x) {
+    if (x) return x * 2;
+    yield 1;
+}
+
+expect(gen(2).next()).toEqual({ value: 4, done: true });
+const it = gen(0);
+expect(it.next()).toEqual({ value: 1, done: false });
+expect(it.next()).toEqual({ value: undefined, done: true });
+"
+);
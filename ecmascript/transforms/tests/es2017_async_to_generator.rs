@@ -95,10 +95,10 @@ let TestClass = {
      name: 'John Doe',
      testMethodFailure () {
         return new Promise((function(resolve) {
-          var _ref = _asyncToGenerator((function*(resolve) {
+          var _ref = _asyncToGenerator(function*(resolve) {
             console.log(this);
             setTimeout(resolve, 1000);
-          }).bind(this));
+          });
           return function() {
             return _ref.apply(this, arguments);
           };
@@ -225,21 +225,21 @@ async function s(x, ...args) {
 "#,
     r#"
 function _s() {
-    _s = _asyncToGenerator((function*(x) {
+    _s = _asyncToGenerator(function*(x) {
         for(let _len = arguments.length, args = new Array(_len > 1 ? _len - 1 : 0), _key = 1; _key < _len; _key++){
             args[_key - 1] = arguments[_key];
         }
         let t = (function(y, a) {
-            var _ref = _asyncToGenerator((function*(y, a) {
+            var _ref = _asyncToGenerator(function*(y, a) {
                 let r = (function(z, b) {
-                    var _ref1 = _asyncToGenerator((function*(z, b) {
+                    var _ref1 = _asyncToGenerator(function*(z, b) {
                         for(let _len1 = arguments.length, innerArgs = new Array(_len1 > 2 ? _len1 - 2 : 0), _key1 = 2; _key1 < _len1; _key1++){
                             innerArgs[_key1 - 2] = arguments[_key1];
                         }
                         yield z;
                         console.log(this, innerArgs, arguments);
                         return this.x;
-                    }).bind(this));
+                    });
                     return function() {
                         return _ref1.apply(this, arguments);
                     };
@@ -247,14 +247,14 @@ function _s() {
                 yield r();
                 console.log(this, args, arguments);
                 return this.g(r);
-            }).bind(this));
-            return function () {
+            });
+            return function() {
                 return _ref.apply(this, arguments);
             };
         })().bind(this);
         yield t();
         return this.h(t);
-    }).bind(this));
+    });
     return _s.apply(this, arguments);
 }
 function s(x) {
@@ -394,9 +394,9 @@ class Class{
                         this;
                     }).bind(this);
                     (function() {
-                        var _ref = _asyncToGenerator((function*() {
+                        var _ref = _asyncToGenerator(function*() {
                             this;
-                        }).bind(this));
+                        });
                         return function() {
                             return _ref.apply(this, arguments);
                         };
@@ -409,9 +409,9 @@ class Class{
                     this;
                 }).bind(this);
                 (function() {
-                    var _ref = _asyncToGenerator((function*() {
+                    var _ref = _asyncToGenerator(function*() {
                         this;
-                    }).bind(this));
+                    });
                     return function() {
                         return _ref.apply(this, arguments);
                     };
@@ -991,14 +991,9 @@ class Class{
                     ()=>{
                         this;
                     };
-                    (function() {
-                        var _ref = _asyncToGenerator((function*() {
-                            this;
-                        }).bind(this));
-                        return function() {
-                            return _ref.apply(this, arguments);
-                        };
-                    })().bind(this);
+                    _asyncToGenerator((function*() {
+                        this;
+                    }).bind(this));
                 }
             };
             function x() {
@@ -1006,19 +1001,13 @@ class Class{
                 ()=>{
                     this;
                 };
-                (function() {
-                    var _ref = _asyncToGenerator((function*() {
-                        this;
-                    }).bind(this));
-                    return function() {
-                        return _ref.apply(this, arguments);
-                    };
-                })().bind(this);
+                _asyncToGenerator((function*() {
+                    this;
+                }).bind(this));
             }
         }).bind(this))();
     }
 }
-
 "#
 );
 
@@ -1386,15 +1375,10 @@ let TestClass = {
 let TestClass = {
     name: 'John Doe',
     testMethodFailure () {
-        return new Promise((function(resolve) {
-            var _ref = _asyncToGenerator((function*(resolve) {
-                console.log(this);
-                setTimeout(resolve, 1000);
-            }).bind(this));
-            return function() {
-                return _ref.apply(this, arguments);
-            };
-        })().bind(this));
+        return new Promise(_asyncToGenerator((function*(resolve) {
+            console.log(this);
+            setTimeout(resolve, 1000);
+        }).bind(this)));
     }
 };
 
@@ -1727,38 +1711,25 @@ async function s(x, ...args) {
 "#,
     r#"
 function _s() {
-    _s = _asyncToGenerator((function*(x, ...args) {
-        let t = (function(y, a) {
-            var _ref = _asyncToGenerator((function*(y, a) {
-                let r = (function(z, b) {
-                    var _ref1 = _asyncToGenerator((function*(z, b, ...innerArgs) {
-                        yield z;
-                        console.log(this, innerArgs, arguments);
-                        return this.x;
-                    }).bind(this));
-                    return function() {
-                        return _ref1.apply(this, arguments);
-                    };
-                })().bind(this);
-                yield r();
-                console.log(this, args, arguments);
-                return this.g(r);
+    _s = _asyncToGenerator(function*(x, ...args) {
+        let t = _asyncToGenerator((function*(y, a) {
+            let r = _asyncToGenerator((function*(z, b, ...innerArgs) {
+                yield z;
+                console.log(this, innerArgs, arguments);
+                return this.x;
             }).bind(this));
-            return function() {
-                return _ref.apply(this, arguments);
-            };
-        })().bind(this);
+            yield r();
+            console.log(this, args, arguments);
+            return this.g(r);
+        }).bind(this));
         yield t();
         return this.h(t);
-    }).bind(this));
+    });
     return _s.apply(this, arguments);
 }
-
-
 function s(x) {
-  return _s.apply(this, arguments);
+    return _s.apply(this, arguments);
 }
-
 "#
 );
 
@@ -2128,3 +2099,94 @@ function foo() {
     return _foo.apply(this, arguments);
 }"
 );
+
+test!(
+    syntax(),
+    |_| chain!(async_to_generator(), fixer()),
+    async_arrow_this,
+    r#"
+class Foo {
+  method() {
+    return (async () => {
+      await bar();
+      return this;
+    })();
+  }
+}
+"#,
+    r#"
+class Foo {
+  method() {
+    return _asyncToGenerator((function*() {
+      yield bar();
+      return this;
+    }).bind(this))();
+  }
+}
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| chain!(async_to_generator(), arrow()),
+    async_arrow_in_class_method_exec,
+    r#"
+class Foo {
+  method() {
+    return (async () => {
+      await null;
+      return (async () => this)();
+    })();
+  }
+}
+
+const foo = new Foo();
+return foo.method().then((res) => {
+  expect(res).toBe(foo);
+});
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| chain!(async_to_generator(), es2015(Default::default())),
+    async_arrow_in_class_method_es2015_exec,
+    r#"
+class Foo {
+  method() {
+    return (async () => this)();
+  }
+}
+
+const foo = new Foo();
+return foo.method().then((res) => {
+  expect(res).toBe(foo);
+});
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| chain!(arrow(), async_to_generator()),
+    async_fn_this_per_call_exec,
+    r#"
+async function getThis() {
+  return this;
+}
+const fn = async function () {
+  return this;
+};
+
+const a = {}, b = {};
+return Promise.all([
+  getThis.call(a),
+  getThis.call(b),
+  fn.call(a),
+  fn.call(b),
+]).then((res) => {
+  expect(res).toEqual([a, b, a, b]);
+  expect(res[1]).toBe(b);
+  expect(res[3]).toBe(b);
+});
+"#
+);
@@ -3888,7 +3888,7 @@ test!(
     |_| chain!(
         resolver(),
         compat::es2015::BlockScopedFns,
-        compat::es2015::block_scoping(),
+        compat::es2015::block_scoping(Default::default()),
         common_js(Default::default()),
     ),
     issue_396_2,
@@ -4076,3 +4076,66 @@ Object.keys(_c).forEach(function(key) {
 });
 "
 );
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    live_binding_before_export,
+    "let foo = 1;
+foo = 2;
+export { foo as bar };",
+    "'use strict';
+Object.defineProperty(exports, '__esModule', {
+    value: true
+});
+exports.bar = void 0;
+let foo = 1;
+exports.bar = foo = 2;
+exports.bar = foo;"
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    live_binding_in_declaration,
+    "export let foo = function () {
+    foo = 1;
+};
+export function bar() {
+    bar = 2;
+}",
+    "'use strict';
+Object.defineProperty(exports, '__esModule', {
+    value: true
+});
+exports.bar = bar;
+exports.foo = void 0;
+let foo = function () {
+    exports.foo = foo = 1;
+};
+exports.foo = foo;
+function bar() {
+    exports.bar = bar = 2;
+}"
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    live_binding_nested_var,
+    "if (cond) {
+    var foo = 1;
+}
+foo = 2;
+export { foo };",
+    "'use strict';
+Object.defineProperty(exports, '__esModule', {
+    value: true
+});
+exports.foo = void 0;
+if (cond) {
+    var foo = 1;
+}
+exports.foo = foo = 2;
+exports.foo = foo;"
+);
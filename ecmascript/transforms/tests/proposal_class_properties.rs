@@ -39,7 +39,7 @@ fn tr() -> impl Pass {
         function_name(),
         class_properties(),
         Classes::default(),
-        block_scoping(),
+        block_scoping(Default::default()),
         ReservedWord {
             preserve_import: false
         },
@@ -468,7 +468,7 @@ function () {
 
   function Foo() {
     _classCallCheck(this, Foo);
-    _defineProperty(this, "foo", function () {
+    _defineProperty(this, "foo", function foo() {
       return this;
     });
   }
@@ -743,12 +743,12 @@ call(function() {
     _defineProperty(_class, 'test', true);
     return _class;
 }());
-var _class = function _class() {
+var _default = function _default() {
   'use strict';
-  _classCallCheck(this, _class);
+  _classCallCheck(this, _default);
 };
-_defineProperty(_class, 'test', true);
-export { _class as default }
+_defineProperty(_default, 'test', true);
+export { _default as default }
 "#
 );
 
@@ -2876,7 +2876,11 @@ let Foo = function Foo(bar) {
 
 test!(
     syntax(),
-    |_| chain!(resolver(), class_properties(), block_scoping()),
+    |_| chain!(
+        resolver(),
+        class_properties(),
+        block_scoping(Default::default())
+    ),
     issue_443,
     "
 const MODE = 1;
@@ -2926,14 +2930,9 @@ export default class MyClass3 {
     r#"
 class MyClass {
   constructor() {
-    _defineProperty(this, 'myAsyncMethod', (function() {
-        var _ref = _asyncToGenerator((function*() {
-            console.log(this);
-        }).bind(this));
-        return function() {
-            return _ref.apply(this, arguments);
-        };
-    })().bind(this));
+    _defineProperty(this, 'myAsyncMethod', _asyncToGenerator((function*() {
+        console.log(this);
+    }).bind(this)));
 
   }
 
@@ -2942,14 +2941,9 @@ class MyClass {
 (function() {
     class MyClass2{
         constructor(){
-            _defineProperty(this, 'myAsyncMethod', (function() {
-                var _ref = _asyncToGenerator((function*() {
-                    console.log(this);
-                }).bind(this));
-                return function() {
-                    return _ref.apply(this, arguments);
-                };
-            })().bind(this));
+            _defineProperty(this, 'myAsyncMethod', _asyncToGenerator((function*() {
+                console.log(this);
+            }).bind(this)));
         }
     }
     return MyClass2;
@@ -2958,14 +2952,9 @@ class MyClass {
 
 class MyClass3{
     constructor(){
-        _defineProperty(this, 'myAsyncMethod', (function() {
-            var _ref = _asyncToGenerator((function*() {
-                console.log(this);
-            }).bind(this));
-            return function() {
-                return _ref.apply(this, arguments);
-            };
-        })().bind(this));
+        _defineProperty(this, 'myAsyncMethod', _asyncToGenerator((function*() {
+            console.log(this);
+        }).bind(this)));
     }
 }
 export { MyClass3 as default }
@@ -2975,7 +2964,7 @@ export { MyClass3 as default }
 // private_regression_t6719
 test!(
     syntax(),
-    |_| chain!(class_properties(), block_scoping()),
+    |_| chain!(class_properties(), block_scoping(Default::default())),
     private_regression_t6719,
     r#"
 function withContext(ComposedComponent) {
@@ -3061,7 +3050,7 @@ function withContext(ComposedComponent) {
 // private_reevaluated
 test!(
     syntax(),
-    |_| chain!(class_properties(), block_scoping()),
+    |_| chain!(class_properties(), block_scoping(Default::default())),
     private_reevaluated,
     r#"
 function classFactory() {
@@ -3125,7 +3114,7 @@ function classFactory() {
 // private_static
 test!(
     syntax(),
-    |_| chain!(class_properties(), block_scoping()),
+    |_| chain!(class_properties(), block_scoping(Default::default())),
     private_static,
     r#"
 class Foo {
@@ -3171,7 +3160,11 @@ expect(Foo.test()).toBe("foo");
 // private_destructuring_object_pattern_1
 test!(
     syntax(),
-    |_| chain!(class_properties(), Classes::default(), block_scoping()),
+    |_| chain!(
+        class_properties(),
+        Classes::default(),
+        block_scoping(Default::default())
+    ),
     private_destructuring_object_pattern_1,
     r#"
 class Foo {
@@ -3210,7 +3203,7 @@ var _client = new WeakMap();
 // private_static_inherited
 test!(
     syntax(),
-    |_| chain!(class_properties(), block_scoping()),
+    |_| chain!(class_properties(), block_scoping(Default::default())),
     private_static_inherited,
     r#"
 class Base {
@@ -3316,7 +3309,7 @@ expect(foo.z).toBe('bar');
 // private_static_undefined
 test!(
     syntax(),
-    |_| chain!(class_properties(), block_scoping()),
+    |_| chain!(class_properties(), block_scoping(Default::default())),
     private_static_undefined,
     r#"
 class Foo {
@@ -3355,7 +3348,11 @@ var _bar = {
 // private_destructuring_array_pattern
 test!(
     syntax(),
-    |_| chain!(class_properties(), Classes::default(), block_scoping()),
+    |_| chain!(
+        class_properties(),
+        Classes::default(),
+        block_scoping(Default::default())
+    ),
     private_destructuring_array_pattern,
     r#"
 class Foo {
@@ -3389,7 +3386,7 @@ var _client = new WeakMap();
 // private_regression_t2983
 test!(
     syntax(),
-    |_| chain!(class_properties(), block_scoping()),
+    |_| chain!(class_properties(), block_scoping(Default::default())),
     private_regression_t2983,
     r#"
 call(class {
@@ -3426,7 +3423,11 @@ export { _class as default }
 // private_regression_t7364
 test!(
     syntax(),
-    |_| chain!(class_properties(), async_to_generator(), block_scoping()),
+    |_| chain!(
+        class_properties(),
+        async_to_generator(),
+        block_scoping(Default::default())
+    ),
     private_regression_t7364,
     r#"
 class MyClass {
@@ -3453,14 +3454,9 @@ class MyClass{
     constructor(){
         _myAsyncMethod1.set(this, {
             writable: true,
-            value: (function() {
-                var _ref = _asyncToGenerator((function*() {
-                    console.log(this);
-                }).bind(this));
-                return function() {
-                    return _ref.apply(this, arguments);
-                };
-            })().bind(this)
+            value: _asyncToGenerator((function*() {
+                console.log(this);
+            }).bind(this))
         });
     }
 }
@@ -3472,14 +3468,9 @@ var _myAsyncMethod1 = new WeakMap();
         constructor(){
             _myAsyncMethod2.set(this, {
                 writable: true,
-                value: (function() {
-                    var _ref = _asyncToGenerator((function*() {
-                        console.log(this);
-                    }).bind(this));
-                    return function() {
-                        return _ref.apply(this, arguments);
-                    };
-                })().bind(this)
+                value: _asyncToGenerator((function*() {
+                    console.log(this);
+                }).bind(this))
             });
         }
     }
@@ -3492,14 +3483,9 @@ class MyClass3{
     constructor(){
         _myAsyncMethod.set(this, {
             writable: true,
-            value: (function() {
-                var _ref = _asyncToGenerator((function*() {
-                    console.log(this);
-                }).bind(this));
-                return function() {
-                    return _ref.apply(this, arguments);
-                };
-            })().bind(this)
+            value: _asyncToGenerator((function*() {
+                console.log(this);
+            }).bind(this))
         });
     }
 }
@@ -3512,7 +3498,11 @@ export { MyClass3 as default }
 // private_destructuring_array_pattern_1
 test!(
     syntax(),
-    |_| chain!(class_properties(), Classes::default(), block_scoping()),
+    |_| chain!(
+        class_properties(),
+        Classes::default(),
+        block_scoping(Default::default())
+    ),
     private_destructuring_array_pattern_1,
     r#"
 class Foo {
@@ -3740,7 +3730,7 @@ for(let i=0; i<= 10; ++i) {
 // private_static_export
 test!(
     syntax(),
-    |_| chain!(class_properties(), block_scoping()),
+    |_| chain!(class_properties(), block_scoping(Default::default())),
     private_static_export,
     r#"
 export class MyClass {
@@ -4109,7 +4099,11 @@ new ComputedField();
 // private_canonical
 test!(
     syntax(),
-    |_| chain!(class_properties(), Classes::default(), block_scoping()),
+    |_| chain!(
+        class_properties(),
+        Classes::default(),
+        block_scoping(Default::default())
+    ),
     private_canonical,
     r#"
 class Point {
@@ -4275,7 +4269,11 @@ class C {
 // private_destructuring_array_pattern_3
 test!(
     syntax(),
-    |_| chain!(class_properties(), Classes::default(), block_scoping()),
+    |_| chain!(
+        class_properties(),
+        Classes::default(),
+        block_scoping(Default::default())
+    ),
     private_destructuring_array_pattern_3,
     r#"
 class Foo {
@@ -4333,7 +4331,11 @@ expect(getPropA()).toBe(1);
 // private_destructuring_array_pattern_2
 test!(
     syntax(),
-    |_| chain!(class_properties(), Classes::default(), block_scoping()),
+    |_| chain!(
+        class_properties(),
+        Classes::default(),
+        block_scoping(Default::default())
+    ),
     private_destructuring_array_pattern_2,
     r#"
 class Foo {
@@ -4366,7 +4368,7 @@ var _client = new WeakMap();
 // private_non_block_arrow_func
 test!(
     syntax(),
-    |_| chain!(class_properties(), block_scoping()),
+    |_| chain!(class_properties(), block_scoping(Default::default())),
     private_non_block_arrow_func,
     r#"
 export default param =>
@@ -4511,7 +4513,7 @@ test!(
         class_properties(),
         exponentation(),
         Classes::default(),
-        block_scoping(),
+        block_scoping(Default::default()),
     ),
     private_instance,
     r#"
@@ -4565,7 +4567,7 @@ _defineProperty(C, _ref, 3);
 // public_native_classes
 test!(
     syntax(),
-    |_| chain!(class_properties(), block_scoping()),
+    |_| chain!(class_properties(), block_scoping(Default::default())),
     public_native_classes,
     r#"
 class Foo {
@@ -4617,7 +4619,7 @@ test!(
     // Seems useless, while being hard to implement.
     ignore,
     syntax(),
-    |_| chain!(class_properties(), block_scoping()),
+    |_| chain!(class_properties(), block_scoping(Default::default())),
     private_static_infer_name,
     r#"
 var Foo = class {
@@ -4665,7 +4667,7 @@ _defineProperty(Foo, "foo", {});
 // private_native_classes
 test!(
     syntax(),
-    |_| chain!(class_properties(), block_scoping()),
+    |_| chain!(class_properties(), block_scoping(Default::default())),
     private_native_classes,
     r#"
 class Foo {
@@ -4713,7 +4715,11 @@ var _bar = new WeakMap();
 // public_computed_without_block
 test!(
     syntax(),
-    |_| chain!(class_properties(), Classes::default(), block_scoping()),
+    |_| chain!(
+        class_properties(),
+        Classes::default(),
+        block_scoping(Default::default())
+    ),
     public_computed_without_block,
     r#"
 const createClass = (k) => class { [k()] = 2 };
@@ -4761,7 +4767,11 @@ expect(foo.getClient()).toEqual(['bar', 'baz', 'quu']);
 // public_static_super
 test!(
     syntax(),
-    |_| chain!(class_properties(), Classes::default(), block_scoping()),
+    |_| chain!(
+        class_properties(),
+        Classes::default(),
+        block_scoping(Default::default())
+    ),
     public_static_super,
     r#"
 class A {
@@ -107,8 +107,14 @@ impl<'a, 'b, P: Pass> PassBuilder<'a, 'b, P> {
                         for_of: compat::es2015::for_of::Config {
                             assume_array: self.loose
                         },
+                        classes: compat::es2015::classes::Config { loose: self.loose },
+                        instanceof: Default::default(),
                         spread: compat::es2015::spread::Config { loose: self.loose },
+                        computed_props: compat::es2015::computed_props::Config {
+                            loose: self.loose
+                        },
                         destructuring: compat::es2015::destructuring::Config { loose: self.loose },
+                        block_scoping: Default::default(),
                     }),
                     self.target <= JscTarget::Es2015
                 ),